@@ -1,39 +1,720 @@
-use anyhow::Result;
-use reqwest::header::{ACCEPT, AUTHORIZATION, USER_AGENT};
-use serde::de::DeserializeOwned;
+use anyhow::{anyhow, bail, Result};
+use core::fmt;
+use futures::{future::join_all, stream, StreamExt};
+use reqwest::{
+    header::{HeaderValue, ACCEPT, AUTHORIZATION, USER_AGENT},
+    Method, RequestBuilder, Response, StatusCode,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
 
-use crate::models::{config::Config, issue::Issue, repository::Repository};
+use crate::models::{
+    checks::{CheckRuns, Checks, CombinedStatus},
+    comment::Comment,
+    config::Profile,
+    discussion::{Discussion, DiscussionComment},
+    graphql::{Connection, GraphqlRequest, GraphqlResponse},
+    issue::{Issue, NewIssue},
+    issue_template::IssueTemplate,
+    project::{Project, ProjectField},
+    pull_request::{MergeRequest, MergeResult, PullRequest},
+    release::Release,
+    repository::Repository,
+    review::{NewReview, Review, ReviewEvent},
+    user::{AuthenticatedUser, User, UserProfile},
+    workflow_run::{Job, Jobs, WorkflowRun, WorkflowRuns},
+};
 
-async fn fetch_github<'a, T: DeserializeOwned>(config: &Config, url: &str) -> Result<T> {
+/// The error body Github responds with on failed requests.
+#[derive(Deserialize)]
+struct GithubError {
+    message: String,
+}
+
+/// An unsuccessful response from Github.
+#[derive(Debug)]
+pub struct RequestError {
+    pub status: StatusCode,
+    pub message: String,
+}
+
+impl fmt::Display for RequestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.message, self.status)
+    }
+}
+
+impl std::error::Error for RequestError {}
+
+/// The url of `path` on the API of the profile's host, api.github.com when no host is set.
+fn api_url(profile: &Profile, path: &str) -> String {
+    match profile.host.trim_end_matches('/') {
+        "" | "https://github.com" => format!("https://api.github.com/{}", path),
+        // Github Enterprise Server serves GraphQL outside of the REST prefix
+        host if path == "graphql" => format!("{}/api/graphql", host),
+        host => format!("{}/api/v3/{}", host, path),
+    }
+}
+
+fn github_request(profile: &Profile, method: Method, url: &str) -> RequestBuilder {
     let client = reqwest::Client::new();
 
-    let raw = client
-        .get(format!("https://api.github.com/{}", url))
-        .header(
-            AUTHORIZATION,
-            format!("Bearer {}", &config.github_access_token),
-        )
+    client
+        .request(method, api_url(profile, url))
+        .header(AUTHORIZATION, format!("Bearer {}", profile.token))
         .header(ACCEPT, "application/vnd.github+json")
         .header("X-GitHub-Api-Version", "2022-11-28")
-        .header(USER_AGENT, &config.user_name)
+        // The user name may not be known before the first request
+        .header(USER_AGENT, env!("CARGO_PKG_NAME"))
+}
+
+/// Turn an unsuccessful response into an error carrying Github's message.
+///
+/// Also used for Gitea, which responds with errors of the same shape.
+pub async fn check_response(raw: Response) -> Result<Response> {
+    let status = raw.status();
+
+    if !status.is_success() {
+        let message = match raw.json::<GithubError>().await {
+            Ok(error) => error.message,
+            Err(_) => String::from(status.canonical_reason().unwrap_or("Unknown error")),
+        };
+
+        return Err(RequestError { status, message }.into());
+    }
+
+    Ok(raw)
+}
+
+async fn fetch_github<T: DeserializeOwned>(profile: &Profile, url: &str) -> Result<T> {
+    let raw = github_request(profile, Method::GET, url).send().await?;
+
+    Ok(check_response(raw).await?.json::<T>().await?)
+}
+
+async fn send_github<B: Serialize>(
+    profile: &Profile,
+    method: Method,
+    url: &str,
+    body: &B,
+) -> Result<Response> {
+    let raw = github_request(profile, method, url)
+        .json(body)
         .send()
         .await?;
 
-    Ok(raw.json::<T>().await?)
+    check_response(raw).await
+}
+
+async fn fetch_graphql<T: DeserializeOwned>(
+    profile: &Profile,
+    query: &str,
+    variables: Value,
+) -> Result<T> {
+    let response = send_github(
+        profile,
+        Method::POST,
+        "graphql",
+        &GraphqlRequest { query, variables },
+    )
+    .await?
+    .json::<GraphqlResponse<T>>()
+    .await?;
+
+    if let Some(errors) = response.errors.filter(|errors| !errors.is_empty()) {
+        bail!(
+            "{}",
+            errors
+                .into_iter()
+                .map(|error| error.message)
+                .collect::<Vec<String>>()
+                .join(", ")
+        );
+    }
+
+    response
+        .data
+        .ok_or_else(|| anyhow!("Github returned no data"))
+}
+
+/// Split a `{owner}/{repo}` name for use as GraphQL variables.
+fn repository_variables(repo: &str) -> Value {
+    let (owner, name) = repo.split_once('/').unwrap_or(("", repo));
+
+    json!({ "owner": owner, "name": name })
+}
+
+pub async fn fetch_issues_self(profile: &Profile) -> Result<Vec<Issue>> {
+    fetch_github::<Vec<Issue>>(profile, "issues").await
+}
+
+pub async fn fetch_issues_repo(profile: &Profile, repo: &str) -> Result<Vec<Issue>> {
+    fetch_github::<Vec<Issue>>(profile, format!("repos/{}/issues", repo).as_str()).await
+}
+
+pub async fn fetch_issue(profile: &Profile, repo: &str, number: usize) -> Result<Issue> {
+    fetch_github::<Issue>(
+        profile,
+        format!("repos/{}/issues/{}", repo, number).as_str(),
+    )
+    .await
+}
+
+pub async fn fetch_comments(profile: &Profile, repo: &str, number: usize) -> Result<Vec<Comment>> {
+    fetch_github::<Vec<Comment>>(
+        profile,
+        format!("repos/{}/issues/{}/comments?per_page=100", repo, number).as_str(),
+    )
+    .await
+}
+
+pub async fn create_comment(
+    profile: &Profile,
+    repo: &str,
+    number: usize,
+    body: &str,
+) -> Result<Comment> {
+    Ok(send_github(
+        profile,
+        Method::POST,
+        format!("repos/{}/issues/{}/comments", repo, number).as_str(),
+        &json!({ "body": body }),
+    )
+    .await?
+    .json::<Comment>()
+    .await?)
+}
+
+/// Fetch the user the token belongs to with the OAuth scopes of the token.
+pub async fn fetch_authenticated_user(profile: &Profile) -> Result<AuthenticatedUser> {
+    let raw = github_request(profile, Method::GET, "user").send().await?;
+    let raw = check_response(raw).await?;
+
+    // Only classic personal access tokens and OAuth tokens list their scopes
+    let scopes = raw
+        .headers()
+        .get("X-OAuth-Scopes")
+        .and_then(|scopes| scopes.to_str().ok())
+        .map(|scopes| {
+            scopes
+                .split(',')
+                .map(|scope| scope.trim().to_string())
+                .filter(|scope| !scope.is_empty())
+                .collect()
+        });
+
+    let user = raw.json::<User>().await?;
+
+    Ok(AuthenticatedUser {
+        login: user.login,
+        scopes,
+    })
+}
+
+pub async fn fetch_user(profile: &Profile, login: &str) -> Result<UserProfile> {
+    fetch_github::<UserProfile>(profile, format!("users/{}", login).as_str()).await
+}
+
+/// Fetch the open issues `login` created in `repo`.
+pub async fn fetch_issues_by(profile: &Profile, repo: &str, login: &str) -> Result<Vec<Issue>> {
+    let issues = fetch_github::<Vec<Issue>>(
+        profile,
+        format!("repos/{}/issues?creator={}&state=open", repo, login).as_str(),
+    )
+    .await?;
+
+    // The issues endpoint lists pull requests as well
+    Ok(issues
+        .into_iter()
+        .filter(|issue| !issue.is_pull_request())
+        .collect())
+}
+
+pub async fn fetch_repositories(profile: &Profile) -> Result<Vec<Repository>> {
+    fetch_github::<Vec<Repository>>(
+        profile,
+        format!("users/{}/repos", profile.user_name).as_str(),
+    )
+    .await
+}
+
+pub async fn fetch_repository(profile: &Profile, repo: &str) -> Result<Repository> {
+    fetch_github::<Repository>(profile, format!("repos/{}", repo).as_str()).await
+}
+
+/// Fetch the raw content of a file `url` points at, `None` when it does not exist.
+async fn fetch_raw(profile: &Profile, url: &str) -> Result<Option<String>> {
+    let mut request = github_request(profile, Method::GET, url).build()?;
+
+    // Ask for the file content instead of its base64 encoded metadata
+    request.headers_mut().insert(
+        ACCEPT,
+        HeaderValue::from_static("application/vnd.github.raw"),
+    );
+
+    let raw = reqwest::Client::new().execute(request).await?;
+
+    if raw.status() == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+
+    Ok(Some(check_response(raw).await?.text().await?))
+}
+
+/// Fetch the raw README of `repo`, `None` when the repository has none.
+pub async fn fetch_readme(profile: &Profile, repo: &str) -> Result<Option<String>> {
+    fetch_raw(profile, format!("repos/{}/readme", repo).as_str()).await
+}
+
+/// An entry of a repository directory listing.
+#[derive(Deserialize)]
+struct ContentEntry {
+    name: String,
+    path: String,
+    #[serde(rename = "type")]
+    kind: String,
+}
+
+/// Fetch and parse the markdown templates and YAML issue forms of `repo`.
+pub async fn fetch_issue_templates(profile: &Profile, repo: &str) -> Result<Vec<IssueTemplate>> {
+    let raw = github_request(
+        profile,
+        Method::GET,
+        format!("repos/{}/contents/.github/ISSUE_TEMPLATE", repo).as_str(),
+    )
+    .send()
+    .await?;
+
+    if raw.status() == StatusCode::NOT_FOUND {
+        return Ok(vec![]);
+    }
+
+    let entries = check_response(raw)
+        .await?
+        .json::<Vec<ContentEntry>>()
+        .await?
+        .into_iter()
+        .filter(|entry| {
+            // `config.yml` configures the template chooser and is not a template
+            entry.kind == "file"
+                && !entry.name.starts_with("config.")
+                && [".md", ".yml", ".yaml"]
+                    .iter()
+                    .any(|extension| entry.name.ends_with(extension))
+        })
+        .collect::<Vec<ContentEntry>>();
+
+    let contents = join_all(entries.iter().map(|entry| async move {
+        let url = format!("repos/{}/contents/{}", repo, entry.path);
+
+        fetch_raw(profile, url.as_str()).await
+    }))
+    .await;
+
+    let mut templates = vec![];
+
+    for (entry, content) in entries.iter().zip(contents) {
+        // A malformed template doesn't keep the others from being offered
+        if let Some(Ok(template)) =
+            content?.map(|content| IssueTemplate::parse(entry.name.as_str(), content.as_str()))
+        {
+            templates.push(template);
+        }
+    }
+
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(templates)
+}
+
+pub async fn create_issue(profile: &Profile, repo: &str, issue: &NewIssue) -> Result<Issue> {
+    Ok(send_github(
+        profile,
+        Method::POST,
+        format!("repos/{}/issues", repo).as_str(),
+        issue,
+    )
+    .await?
+    .json::<Issue>()
+    .await?)
+}
+
+pub async fn fetch_releases(profile: &Profile, repo: &str) -> Result<Vec<Release>> {
+    fetch_github::<Vec<Release>>(profile, format!("repos/{}/releases", repo).as_str()).await
 }
 
-pub async fn fetch_issues_self(config: &Config) -> Result<Vec<Issue>> {
-    Ok(fetch_github::<Vec<Issue>>(config, "issues").await?)
+pub async fn fetch_workflow_runs(profile: &Profile, repo: &str) -> Result<Vec<WorkflowRun>> {
+    Ok(fetch_github::<WorkflowRuns>(
+        profile,
+        format!("repos/{}/actions/runs?per_page=30", repo).as_str(),
+    )
+    .await?
+    .workflow_runs)
 }
 
-pub async fn fetch_issues_repo(config: &Config, repo: &str) -> Result<Vec<Issue>> {
-    Ok(fetch_github::<Vec<Issue>>(config, format!("repos/{}/issues", repo).as_str()).await?)
+pub async fn fetch_jobs(profile: &Profile, repo: &str, run_id: u64) -> Result<Vec<Job>> {
+    Ok(fetch_github::<Jobs>(
+        profile,
+        format!("repos/{}/actions/runs/{}/jobs", repo, run_id).as_str(),
+    )
+    .await?
+    .jobs)
 }
 
-pub async fn fetch_repositories(config: &Config) -> Result<Vec<Repository>> {
-    Ok(fetch_github::<Vec<Repository>>(
-        config,
-        format!("users/{}/repos", config.user_name).as_str(),
+pub async fn fetch_job_logs(profile: &Profile, repo: &str, job_id: u64) -> Result<String> {
+    // Github redirects to a short lived plain text download
+    let raw = github_request(
+        profile,
+        Method::GET,
+        format!("repos/{}/actions/jobs/{}/logs", repo, job_id).as_str(),
     )
+    .send()
+    .await?;
+
+    Ok(check_response(raw).await?.text().await?)
+}
+
+pub async fn rerun_failed_jobs(profile: &Profile, repo: &str, run_id: u64) -> Result<()> {
+    let raw = github_request(
+        profile,
+        Method::POST,
+        format!("repos/{}/actions/runs/{}/rerun-failed-jobs", repo, run_id).as_str(),
+    )
+    .send()
+    .await?;
+
+    check_response(raw).await?;
+
+    Ok(())
+}
+
+pub async fn cancel_workflow_run(profile: &Profile, repo: &str, run_id: u64) -> Result<()> {
+    let raw = github_request(
+        profile,
+        Method::POST,
+        format!("repos/{}/actions/runs/{}/cancel", repo, run_id).as_str(),
+    )
+    .send()
+    .await?;
+
+    check_response(raw).await?;
+
+    Ok(())
+}
+
+pub async fn fetch_pull_request(
+    profile: &Profile,
+    repo: &str,
+    number: usize,
+) -> Result<PullRequest> {
+    fetch_github::<PullRequest>(profile, format!("repos/{}/pulls/{}", repo, number).as_str()).await
+}
+
+/// Fetch the combined commit status and check runs of a commit.
+pub async fn fetch_checks(profile: &Profile, repo: &str, sha: &str) -> Result<Checks> {
+    let status_url = format!("repos/{}/commits/{}/status", repo, sha);
+    let check_runs_url = format!("repos/{}/commits/{}/check-runs", repo, sha);
+
+    let (status, check_runs) = tokio::join!(
+        fetch_github::<CombinedStatus>(profile, status_url.as_str()),
+        fetch_github::<CheckRuns>(profile, check_runs_url.as_str()),
+    );
+
+    let checks = status?
+        .statuses
+        .into_iter()
+        .map(Into::into)
+        .chain(check_runs?.check_runs.into_iter().map(Into::into))
+        .collect();
+
+    Ok(Checks::new(checks))
+}
+
+pub async fn fetch_reviews(profile: &Profile, repo: &str, number: usize) -> Result<Vec<Review>> {
+    fetch_github::<Vec<Review>>(
+        profile,
+        format!("repos/{}/pulls/{}/reviews", repo, number).as_str(),
+    )
+    .await
+}
+
+pub async fn submit_review(
+    profile: &Profile,
+    repo: &str,
+    number: usize,
+    event: ReviewEvent,
+    body: String,
+) -> Result<Review> {
+    Ok(send_github(
+        profile,
+        Method::POST,
+        format!("repos/{}/pulls/{}/reviews", repo, number).as_str(),
+        &NewReview { body, event },
+    )
+    .await?
+    .json::<Review>()
+    .await?)
+}
+
+pub async fn merge_pull_request(
+    profile: &Profile,
+    repo: &str,
+    number: usize,
+    merge: &MergeRequest,
+) -> Result<MergeResult> {
+    Ok(send_github(
+        profile,
+        Method::PUT,
+        format!("repos/{}/pulls/{}/merge", repo, number).as_str(),
+        merge,
+    )
+    .await?
+    .json::<MergeResult>()
     .await?)
 }
+
+pub async fn delete_branch(profile: &Profile, repo: &str, branch: &str) -> Result<()> {
+    let raw = github_request(
+        profile,
+        Method::DELETE,
+        format!("repos/{}/git/refs/heads/{}", repo, branch).as_str(),
+    )
+    .send()
+    .await?;
+
+    check_response(raw).await?;
+
+    Ok(())
+}
+
+async fn fetch_pull_request_checks(profile: &Profile, repo: &str, number: usize) -> Result<Checks> {
+    let pull_request = fetch_pull_request(profile, repo, number).await?;
+
+    fetch_checks(profile, repo, pull_request.head.sha.as_str()).await
+}
+
+/// How many pull requests have their details fetched at once, each taking four requests.
+const PULL_REQUEST_DETAILS_CONCURRENCY: usize = 4;
+
+/// Fill in the checks and reviews of every pull request in `issues`.
+///
+/// Pull requests whose details fail to load are left without them.
+pub async fn fetch_pull_request_details(profile: &Profile, issues: &mut [Issue]) {
+    let pull_requests: Vec<_> = issues
+        .iter()
+        .map(|issue| {
+            issue
+                .is_pull_request()
+                .then(|| (issue.repository_name().to_string(), issue.number))
+        })
+        .collect();
+
+    let details: Vec<_> = stream::iter(pull_requests)
+        .map(|pull_request| async move {
+            let Some((repo, number)) = pull_request else {
+                return (None, vec![]);
+            };
+
+            let (checks, reviews) = tokio::join!(
+                fetch_pull_request_checks(profile, repo.as_str(), number),
+                fetch_reviews(profile, repo.as_str(), number),
+            );
+
+            (checks.ok(), reviews.unwrap_or_default())
+        })
+        // Keeps the order of `issues` for zipping the details back in
+        .buffered(PULL_REQUEST_DETAILS_CONCURRENCY)
+        .collect()
+        .await;
+
+    for (issue, (checks, reviews)) in issues.iter_mut().zip(details) {
+        issue.checks = checks;
+        issue.reviews = reviews;
+    }
+}
+
+const DISCUSSIONS_QUERY: &str = "
+query($owner: String!, $name: String!) {
+  repository(owner: $owner, name: $name) {
+    discussions(first: 50, orderBy: { field: UPDATED_AT, direction: DESC }) {
+      nodes {
+        id number title body url isAnswered authorAssociation
+        author { login }
+        category { name }
+        comments(first: 50) {
+          nodes {
+            id body isAnswer authorAssociation
+            author { login }
+            replies(first: 50) { nodes { id body author { login } } }
+          }
+        }
+      }
+    }
+  }
+}";
+
+#[derive(Deserialize)]
+struct DiscussionsData {
+    repository: DiscussionsRepository,
+}
+
+#[derive(Deserialize)]
+struct DiscussionsRepository {
+    discussions: Connection<Discussion>,
+}
+
+/// Fetch the latest discussions of a repository, grouped by category.
+pub async fn fetch_discussions(profile: &Profile, repo: &str) -> Result<Vec<Discussion>> {
+    let data =
+        fetch_graphql::<DiscussionsData>(profile, DISCUSSIONS_QUERY, repository_variables(repo))
+            .await?;
+
+    let mut discussions = data.repository.discussions.nodes;
+    discussions.sort_by(|a, b| a.category.name.cmp(&b.category.name));
+
+    Ok(discussions)
+}
+
+const ADD_DISCUSSION_COMMENT_MUTATION: &str = "
+mutation($discussionId: ID!, $replyToId: ID, $body: String!) {
+  addDiscussionComment(input: { discussionId: $discussionId, replyToId: $replyToId, body: $body }) {
+    comment { id body isAnswer authorAssociation author { login } }
+  }
+}";
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AddDiscussionCommentData {
+    add_discussion_comment: AddDiscussionCommentPayload,
+}
+
+#[derive(Deserialize)]
+struct AddDiscussionCommentPayload {
+    comment: DiscussionComment,
+}
+
+/// Comment on a discussion, threaded under the top level comment `reply_to` if set.
+pub async fn reply_discussion(
+    profile: &Profile,
+    discussion_id: &str,
+    reply_to: Option<&str>,
+    body: String,
+) -> Result<DiscussionComment> {
+    let data = fetch_graphql::<AddDiscussionCommentData>(
+        profile,
+        ADD_DISCUSSION_COMMENT_MUTATION,
+        json!({ "discussionId": discussion_id, "replyToId": reply_to, "body": body }),
+    )
+    .await?;
+
+    Ok(data.add_discussion_comment.comment)
+}
+
+const PROJECTS_QUERY: &str = "
+query($owner: String!, $name: String!) {
+  repository(owner: $owner, name: $name) {
+    projectsV2(first: 20) {
+      nodes {
+        id title
+        fields(first: 50) {
+          nodes {
+            __typename
+            ... on ProjectV2SingleSelectField { id name options { id name } }
+            ... on ProjectV2IterationField { id name configuration { iterations { id title } } }
+          }
+        }
+        items(first: 100) {
+          nodes {
+            id
+            content {
+              ... on Issue { title number url }
+              ... on PullRequest { title number url }
+              ... on DraftIssue { title }
+            }
+            fieldValues(first: 20) {
+              nodes {
+                __typename
+                ... on ProjectV2ItemFieldSingleSelectValue {
+                  optionId
+                  field { ... on ProjectV2SingleSelectField { id } }
+                }
+                ... on ProjectV2ItemFieldIterationValue {
+                  iterationId
+                  field { ... on ProjectV2IterationField { id } }
+                }
+              }
+            }
+          }
+        }
+      }
+    }
+  }
+}";
+
+#[derive(Deserialize)]
+struct ProjectsData {
+    repository: ProjectsRepository,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProjectsRepository {
+    projects_v2: Connection<Project>,
+}
+
+/// Fetch the projects linked to a repository.
+pub async fn fetch_projects(profile: &Profile, repo: &str) -> Result<Vec<Project>> {
+    let data =
+        fetch_graphql::<ProjectsData>(profile, PROJECTS_QUERY, repository_variables(repo)).await?;
+
+    Ok(data.repository.projects_v2.nodes)
+}
+
+const UPDATE_FIELD_MUTATION: &str = "
+mutation($projectId: ID!, $itemId: ID!, $fieldId: ID!, $value: ProjectV2FieldValue!) {
+  updateProjectV2ItemFieldValue(
+    input: { projectId: $projectId, itemId: $itemId, fieldId: $fieldId, value: $value }
+  ) {
+    projectV2Item { id }
+  }
+}";
+
+const CLEAR_FIELD_MUTATION: &str = "
+mutation($projectId: ID!, $itemId: ID!, $fieldId: ID!) {
+  clearProjectV2ItemFieldValue(
+    input: { projectId: $projectId, itemId: $itemId, fieldId: $fieldId }
+  ) {
+    projectV2Item { id }
+  }
+}";
+
+/// Set a single select or iteration field of a project item, clearing it when `value` is `None`.
+pub async fn update_project_field(
+    profile: &Profile,
+    project_id: &str,
+    item_id: &str,
+    field: &ProjectField,
+    value: Option<&str>,
+) -> Result<()> {
+    let mut variables = json!({
+        "projectId": project_id,
+        "itemId": item_id,
+        "fieldId": field.id().unwrap_or(""),
+    });
+
+    let query = match (field, value) {
+        (_, None) => CLEAR_FIELD_MUTATION,
+        (ProjectField::Iteration { .. }, Some(value)) => {
+            variables["value"] = json!({ "iterationId": value });
+            UPDATE_FIELD_MUTATION
+        }
+        (_, Some(value)) => {
+            variables["value"] = json!({ "singleSelectOptionId": value });
+            UPDATE_FIELD_MUTATION
+        }
+    };
+
+    fetch_graphql::<Value>(profile, query, variables).await?;
+
+    Ok(())
+}
@@ -1,177 +1,1709 @@
-use anyhow::Result;
-use crossterm::event::{self, Event, KeyCode};
-use itertools::Itertools;
-use termimad::crossterm::style::Stylize;
+use anyhow::{anyhow, bail, Result};
+use crossterm::event::{self, Event, KeyCode, KeyModifiers};
+use reqwest::StatusCode;
+use std::sync::Arc;
 use tui::{backend::Backend, Terminal};
 
 use crate::{
-    api_requests::{fetch_issues_repo, fetch_issues_self},
+    api_requests::RequestError,
+    editor,
+    events::{AppEvent, Connection, Events, Load, Loaded},
+    forge::{self, Forge, Unsupported},
+    git,
     models::{
-        errors::Errors, input_mode::InputMode, repository::Repository, screen::Screen,
+        config::{BackendType, Profile},
+        errors::Errors,
+        field_editor::FieldEditor,
+        input_mode::InputMode,
+        issue::Issue,
+        issue_form::IssueForm,
+        issue_template::{IssueTemplate, TemplatePicker},
+        merge_dialog::MergeDialog,
+        profile_picker::ProfilePicker,
+        project::ProjectField,
+        repository::Repository,
+        review::ReviewEvent,
+        screen::Screen,
+        setup_wizard::SetupWizard,
         stateful_list::StatefulList,
+        user::AuthorAssociation,
     },
-    reset_terminal,
-    ui::ui,
+    ui::{setup_ui, ui},
     AppState, MenuItems,
 };
 
+/// Ask for the values missing in `profile` until its token is accepted, `None` when the setup
+/// was quit.
+pub async fn run_setup<B: Backend>(
+    terminal: &mut Terminal<B>,
+    mut wizard: SetupWizard,
+    profile: Profile,
+) -> Result<Option<Profile>> {
+    loop {
+        terminal.draw(|f| setup_ui(f, &wizard))?;
+
+        if let Event::Key(key) = event::read()? {
+            match key.code {
+                KeyCode::Up | KeyCode::BackTab => wizard.previous(),
+                KeyCode::Down | KeyCode::Tab => wizard.next(),
+                KeyCode::Char(c) => wizard.type_char(c),
+                KeyCode::Backspace => wizard.backspace(),
+                KeyCode::Enter => {
+                    let mut profile = wizard.apply(&profile);
+
+                    // The local backend reads issues from disk without a token
+                    if profile.token.is_empty() && profile.backend != BackendType::Local {
+                        wizard.message = String::from("A token is required.");
+                        continue;
+                    }
+
+                    wizard.message = String::from("Checking the token..");
+                    terminal.draw(|f| setup_ui(f, &wizard))?;
+
+                    match forge::connect(&profile).fetch_authenticated_user().await {
+                        Ok(user) => {
+                            if profile.user_name.is_empty() {
+                                profile.user_name = user.login;
+                            }
+
+                            if profile.user_name.is_empty() {
+                                wizard.message = String::from("A user name is required.");
+                            } else {
+                                return Ok(Some(profile));
+                            }
+                        }
+                        Err(err) => {
+                            wizard.message = format!("{} {}", Errors::InvalidTokenError, err)
+                        }
+                    }
+                }
+                KeyCode::Esc => return Ok(None),
+
+                _ => {}
+            }
+        }
+    }
+}
+
 pub async fn run_app<B: Backend>(
     terminal: &mut Terminal<B>,
     mut app_state: AppState,
+    mut events: Events,
 ) -> Result<()> {
     loop {
+        load_comments(&mut app_state);
+        load_commits(&mut app_state);
+
         terminal.draw(|f| ui(f, &mut app_state))?;
 
-        if let Event::Key(key) = event::read()? {
-            match app_state.input_mode {
-                InputMode::Normal => {
-                    match key.code {
-                        // Menu switcher
-                        KeyCode::Char('I') => app_state.current_menu = MenuItems::Issues,
-
-                        // Focus switcher
-                        KeyCode::Tab => app_state.change_focus(),
-
-                        // Issue controls
-                        KeyCode::Up | KeyCode::Char('k') => match app_state.screen {
-                            Screen::Issues => app_state.issues.previous(),
-                            Screen::Repositories => app_state.repositories.previous(),
-                            Screen::Error => {}
-                        },
-                        KeyCode::Down | KeyCode::Char('j') => match app_state.screen {
-                            Screen::Issues => app_state.issues.next(),
-                            Screen::Repositories => app_state.repositories.next(),
-                            Screen::Error => {}
-                        },
-
-                        // Fetch all issues assigned to you
-                        KeyCode::Char('M') => {
-                            // This blocks input
-                            match fetch_issues_self(&app_state.config).await {
-                                Ok(issues) => {
-                                    app_state.issues = StatefulList::with_items(issues.clone());
-                                }
-                                Err(_) => {
-                                    app_state.show_error(Errors::FetchRequestError.to_string())
-                                }
-                            }
-                        }
-
-                        KeyCode::Enter => match app_state.screen {
-                            Screen::Issues => {
-                                if let Some(issue) = app_state.issues.selected_value() {
-                                    // Open issue in browser
-                                    webbrowser::open(issue.html_url.as_str()).unwrap_or_else(
-                                        |err| {
-                                            eprintln!("{}: {}", "Error".red().bold(), err);
-                                            reset_terminal().unwrap_or_else(|_| {
-                                                panic!("Failed to reset terminal")
-                                            });
-                                            std::process::exit(1);
-                                        },
-                                    );
-                                }
-                            }
-                            Screen::Repositories => {
-                                if let Some(repo) = app_state.repositories.selected_value() {
-                                    // Maybe a better way than so much cloning here
-                                    let repo = repo.clone();
-
-                                    // and here
-                                    app_state.select_repo(repo.clone());
-
-                                    // Check cache for issues
-                                    match app_state.issue_cache.get(&repo.full_name) {
-                                        Some(issues) => {
-                                            app_state.issues =
-                                                StatefulList::with_items(issues.clone());
-                                        }
-                                        // Fetch issues for repo and add to cache
-                                        None => {
-                                            // This blocks input
-                                            match fetch_issues_repo(
-                                                &app_state.config,
-                                                repo.full_name.as_str(),
-                                            )
+        let key = match events.next().await {
+            Some(AppEvent::Key(key)) => key,
+            Some(AppEvent::Tick) => {
+                app_state.ticks += 1;
+                continue;
+            }
+            Some(AppEvent::Refresh) => {
+                refresh_issues(&mut app_state, true);
+                continue;
+            }
+            Some(AppEvent::Loaded(generation, load, loaded)) => {
+                finish_load(terminal, &mut app_state, generation, load, loaded);
+                continue;
+            }
+            None => return Ok(()),
+        };
+
+        match app_state.input_mode {
+            InputMode::Normal => {
+                match key.code {
+                    // Menu switcher
+                    KeyCode::Char('I') => app_state.set_menu(MenuItems::Issues),
+                    KeyCode::Char('D') => {
+                        app_state.set_menu(MenuItems::Discussions);
+                        load_menu(&mut app_state);
+                    }
+                    KeyCode::Char('E') => {
+                        app_state.set_menu(MenuItems::Releases);
+                        load_menu(&mut app_state);
+                    }
+                    KeyCode::Char('A') => {
+                        app_state.set_menu(MenuItems::Actions);
+                        load_menu(&mut app_state);
+                    }
+                    KeyCode::Char('B') => {
+                        app_state.set_menu(MenuItems::Board);
+                        load_menu(&mut app_state);
+                    }
+
+                    // Focus switcher
+                    KeyCode::Tab => {
+                        app_state.change_focus();
+                        load_overview(&mut app_state);
+                    }
+
+                    // Issue controls
+                    KeyCode::Up | KeyCode::Char('k') => match app_state.screen {
+                        Screen::Issues => app_state.previous_issue(),
+                        Screen::Repositories => {
+                            app_state.repositories.previous();
+                            load_overview(&mut app_state);
+                        }
+                        Screen::Checks => app_state.checks.previous(),
+                        Screen::Comments => app_state.previous_comment(),
+                        Screen::Discussions => app_state.discussions.previous(),
+                        Screen::Releases => app_state.releases.previous(),
+                        Screen::Runs => app_state.previous_run(),
+                        Screen::Jobs => app_state.jobs.previous(),
+                        Screen::Board => {
+                            if let Some(board) = app_state.board.as_mut() {
+                                board.previous_item()
+                            }
+                        }
+                        Screen::Error | Screen::Message => {}
+                    },
+                    KeyCode::Down | KeyCode::Char('j') => match app_state.screen {
+                        Screen::Issues => app_state.next_issue(),
+                        Screen::Repositories => {
+                            app_state.repositories.next();
+                            load_overview(&mut app_state);
+                        }
+                        Screen::Checks => app_state.checks.next(),
+                        Screen::Comments => app_state.next_comment(),
+                        Screen::Discussions => app_state.discussions.next(),
+                        Screen::Releases => app_state.releases.next(),
+                        Screen::Runs => app_state.next_run(),
+                        Screen::Jobs => app_state.jobs.next(),
+                        Screen::Board => {
+                            if let Some(board) = app_state.board.as_mut() {
+                                board.next_item()
+                            }
+                        }
+                        Screen::Error | Screen::Message => {}
+                    },
+
+                    KeyCode::Char('r')
+                        if key.modifiers.contains(KeyModifiers::CONTROL)
+                            && app_state.current_menu == MenuItems::Issues =>
+                    {
+                        // Look for referencing commits again, including failed lookups
+                        app_state.commit_cache.clear();
+                        refresh_issues(&mut app_state, false);
+                    }
+                    // Fetch all issues assigned to you
+                    KeyCode::Char('M') => {
+                        app_state.shows_assigned = true;
+                        app_state.set_issues(vec![]);
+
+                        let forge = app_state.forge.clone();
+                        app_state.spawn_load(Load::IssuesSelf, async move {
+                            Loaded::Issues(fetch_issues_with_details(forge, None).await)
+                        });
+                    }
+
+                    KeyCode::Enter => match app_state.screen {
+                        Screen::Issues => {
+                            if let Some(url) = app_state
+                                .issues
+                                .selected_value()
+                                .map(|issue| issue.html_url.clone())
+                            {
+                                // Open issue in browser
+                                open_in_browser(&mut app_state, url.as_str());
+                            }
+                        }
+                        Screen::Checks => {
+                            if let Some(url) = app_state
+                                .checks
+                                .selected_value()
+                                .and_then(|check| check.url.clone())
+                            {
+                                // Open check run in browser
+                                open_in_browser(&mut app_state, url.as_str());
+                            }
+                        }
+                        Screen::Discussions | Screen::Comments => {
+                            if let Some(url) = app_state
+                                .discussions
+                                .selected_value()
+                                .map(|discussion| discussion.url.clone())
+                            {
+                                // Open discussion in browser
+                                open_in_browser(&mut app_state, url.as_str());
+                            }
+                        }
+                        Screen::Releases => {
+                            if let Some(url) = app_state
+                                .releases
+                                .selected_value()
+                                .map(|release| release.html_url.clone())
+                            {
+                                // Open release in browser
+                                open_in_browser(&mut app_state, url.as_str());
+                            }
+                        }
+                        Screen::Runs => load_jobs(&mut app_state),
+                        Screen::Board => {
+                            if let Some(url) = app_state
+                                .board
+                                .as_ref()
+                                .and_then(|board| board.selected_item())
+                                .and_then(|item| item.content.as_ref())
+                                .and_then(|content| content.url.clone())
+                            {
+                                // Open the item in browser
+                                open_in_browser(&mut app_state, url.as_str());
+                            }
+                        }
+                        Screen::Jobs => {
+                            if let Some(url) = app_state
+                                .jobs
+                                .selected_value()
+                                .and_then(|job| job.html_url.clone())
+                            {
+                                // Open job in browser
+                                open_in_browser(&mut app_state, url.as_str());
+                            }
+                        }
+                        Screen::Repositories => {
+                            if let Some(repo) = app_state.repositories.selected_value() {
+                                // Maybe a better way than so much cloning here
+                                let repo = repo.clone();
+
+                                // and here
+                                app_state.select_repo(repo.clone());
+
+                                load_issues(&mut app_state, repo.full_name);
+                                load_menu(&mut app_state);
+                            }
+                        }
+                        Screen::Error | Screen::Message => app_state.close_message(),
+                    },
+
+                    // Search repo
+                    KeyCode::Char('S') => app_state.show_search(),
+
+                    // Checking out uses Github's pull request refs
+                    KeyCode::Char('O')
+                        if app_state.current_menu == MenuItems::Issues
+                            && app_state.profile.backend != BackendType::Github =>
+                    {
+                        show_unsupported(&mut app_state)
+                    }
+
+                    // Review the selected pull request
+                    KeyCode::Char('R') if app_state.current_menu == MenuItems::Issues => {
+                        match app_state.issues.selected_value() {
+                            Some(issue) if issue.is_pull_request() => {
+                                let number = issue.number;
+                                app_state.show_review(number);
+                            }
+                            _ => app_state.show_error(Errors::NotPullRequestError.to_string()),
+                        }
+                    }
+
+                    // Check out the selected pull request locally
+                    KeyCode::Char('O') if app_state.current_menu == MenuItems::Issues => {
+                        match app_state.issues.selected_value() {
+                            Some(issue) if issue.is_pull_request() => {
+                                let repo = issue.repository_name().to_string();
+                                let number = issue.number;
+                                checkout_pull_request(&mut app_state, repo.as_str(), number);
+                            }
+                            _ => app_state.show_error(Errors::NotPullRequestError.to_string()),
+                        }
+                    }
+
+                    // Switch to another profile
+                    KeyCode::Char('P') => app_state.show_profile_picker(ProfilePicker::new(
+                        app_state.config.active_profile.clone(),
+                        app_state.config.profiles.clone().into_iter().collect(),
+                    )),
+
+                    // Write a new issue for the selected repository
+                    KeyCode::Char('N') if app_state.current_menu == MenuItems::Issues => {
+                        new_issue(&mut app_state)
+                    }
+
+                    // Start working on the selected issue in a new branch
+                    KeyCode::Char('W') if app_state.current_menu == MenuItems::Issues => {
+                        start_work(&mut app_state)
+                    }
+
+                    // Board controls
+                    KeyCode::Left | KeyCode::Char('h') if app_state.screen == Screen::Board => {
+                        if let Some(board) = app_state.board.as_mut() {
+                            board.previous_column()
+                        }
+                    }
+                    KeyCode::Right | KeyCode::Char('l') if app_state.screen == Screen::Board => {
+                        if let Some(board) = app_state.board.as_mut() {
+                            board.next_column()
+                        }
+                    }
+                    KeyCode::Char('H') if app_state.screen == Screen::Board => {
+                        move_project_item(&mut app_state, -1)
+                    }
+                    KeyCode::Char('L') if app_state.screen == Screen::Board => {
+                        move_project_item(&mut app_state, 1)
+                    }
+                    KeyCode::Char('F') if app_state.screen == Screen::Board => {
+                        if let Some(board) = &app_state.board {
+                            if let Some(item) = board.selected_item() {
+                                let field_editor =
+                                    FieldEditor::new(item.id.clone(), board.editable_fields());
+                                app_state.show_field_editor(field_editor);
+                            }
+                        }
+                    }
+                    KeyCode::Char('N') if app_state.current_menu == MenuItems::Board => {
+                        app_state.next_project()
+                    }
+
+                    // Workflow run controls
+                    KeyCode::Char('R') if app_state.current_menu == MenuItems::Actions => {
+                        update_run(&mut app_state, RunUpdate::RerunFailedJobs)
+                    }
+                    KeyCode::Char('X') if app_state.current_menu == MenuItems::Actions => {
+                        update_run(&mut app_state, RunUpdate::Cancel)
+                    }
+                    KeyCode::Char('L') if app_state.current_menu == MenuItems::Actions => {
+                        load_job_logs(&mut app_state)
+                    }
+
+                    // Show the author of the selected item
+                    KeyCode::Char('U')
+                        if matches!(
+                            app_state.current_menu,
+                            MenuItems::Issues | MenuItems::Discussions
+                        ) =>
+                    {
+                        show_author(&mut app_state)
+                    }
+                    // Comment on the selected issue or reply to the selected discussion
+                    KeyCode::Char('C') if app_state.current_menu == MenuItems::Issues => {
+                        comment_on_issue(terminal, &mut app_state)
+                    }
+                    KeyCode::Char('C') if app_state.current_menu == MenuItems::Discussions => {
+                        reply_to_discussion(terminal, &mut app_state)
+                    }
+
+                    // Merge the selected pull request
+                    KeyCode::Char('G') if app_state.current_menu == MenuItems::Issues => {
+                        match app_state.issues.selected_value() {
+                            Some(issue) if issue.is_pull_request() => {
+                                let repo = issue.repository_name().to_string();
+                                let (html_url, number) = (issue.html_url.clone(), issue.number);
+                                let forge = app_state.forge.clone();
+
+                                let load = Load::PullRequest(repo.clone(), html_url);
+                                app_state.spawn_load(load, async move {
+                                    Loaded::PullRequest(
+                                        forge
+                                            .fetch_pull_request(repo.as_str(), number)
                                             .await
-                                            {
-                                                Ok(issues) => {
-                                                    app_state.issues =
-                                                        StatefulList::with_items(issues.clone());
-                                                    app_state.cache_issues(repo.full_name, issues);
-                                                }
-                                                Err(_) => app_state.show_error(
-                                                    Errors::FetchRequestError.to_string(),
-                                                ),
-                                            }
-                                        }
-                                    };
-                                }
-                            }
-                            Screen::Error => app_state.close_error(),
-                        },
-
-                        // Search repo
-                        KeyCode::Char('S') => app_state.show_search(),
-
-                        // Exit keys
-                        KeyCode::Char('q') => return Ok(()),
-
-                        _ => {}
-                    }
-                }
-                InputMode::Editing => match key.code {
-                    KeyCode::Enter => {
-                        let search = app_state.popup.message.trim();
-                        let (_, repo) = app_state
-                            .popup
-                            .message
-                            .split("/")
-                            .next_tuple()
-                            .unwrap_or(("", ""));
-
-                        let repo = Repository {
-                            full_name: String::from(search),
-                            name: String::from(repo),
-                            open_issues_count: 0,
-                        };
-
-                        // Check cache for issues
-                        match app_state.issue_cache.get(&repo.full_name) {
-                            Some(issues) => {
-                                app_state.issues = StatefulList::with_items(issues.clone());
-                                app_state.hide_search();
-                            }
-                            // Fetch issues for repo and add to cache
-                            None => {
-                                // This blocks input
-                                match fetch_issues_repo(&app_state.config, search).await {
-                                    Ok(issues) => {
-                                        app_state.issues = StatefulList::with_items(issues.clone());
-                                        app_state.selected_repo = Some(repo.clone());
-                                        app_state.repositories.items.insert(0, repo.clone());
-                                        app_state.cache_issues(repo.full_name, issues);
-
-                                        app_state.hide_search();
-                                    }
-                                    Err(_) => {
-                                        app_state.show_error(Errors::FetchRequestError.to_string())
-                                    }
-                                }
-                            }
-                        };
-                    }
-                    KeyCode::Char(c) => {
-                        app_state.popup.message.push(c);
-                    }
-                    KeyCode::Backspace => {
-                        app_state.popup.message.pop();
-                    }
-                    KeyCode::Esc => app_state.hide_search(),
+                                            .map(Box::new),
+                                    )
+                                });
+                            }
+                            _ => app_state.show_error(Errors::NotPullRequestError.to_string()),
+                        }
+                    }
+
+                    // Exit keys
+                    KeyCode::Char('q') => return Ok(()),
 
                     _ => {}
+                }
+            }
+            InputMode::Review => {
+                let event = match key.code {
+                    KeyCode::Char('a') => Some(ReviewEvent::Approve),
+                    KeyCode::Char('r') => Some(ReviewEvent::RequestChanges),
+                    KeyCode::Char('c') => Some(ReviewEvent::Comment),
+                    _ => None,
+                };
+
+                if let Some(event) = event {
+                    app_state.hide_review();
+                    review_pull_request(terminal, &mut app_state, event);
+                } else if key.code == KeyCode::Esc {
+                    app_state.hide_review();
+                }
+            }
+            InputMode::Merge => match key.code {
+                KeyCode::Char('e') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    let message = app_state
+                        .merge_dialog
+                        .as_ref()
+                        .map(|merge_dialog| merge_dialog.commit_message.clone())
+                        .unwrap_or_default();
+
+                    match editor::edit(terminal, message.as_str()) {
+                        Ok(message) => app_state
+                            .update_merge(|merge_dialog| merge_dialog.commit_message = message),
+                        Err(err) => {
+                            app_state.hide_merge();
+                            app_state.show_error(format!("{} {}", Errors::EditorError, err));
+                        }
+                    }
+                }
+                KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => app_state
+                    .update_merge(|merge_dialog| {
+                        merge_dialog.delete_branch = !merge_dialog.delete_branch
+                    }),
+                KeyCode::Char(c) => {
+                    app_state.update_merge(|merge_dialog| merge_dialog.commit_title.push(c))
+                }
+                KeyCode::Backspace => app_state.update_merge(|merge_dialog| {
+                    merge_dialog.commit_title.pop();
+                }),
+                KeyCode::Tab => app_state
+                    .update_merge(|merge_dialog| merge_dialog.method = merge_dialog.method.next()),
+                KeyCode::Enter => {
+                    if let Some(merge_dialog) = app_state.hide_merge() {
+                        merge(&mut app_state, merge_dialog);
+                    }
+                }
+                KeyCode::Esc => {
+                    app_state.hide_merge();
+                }
+
+                _ => {}
+            },
+            InputMode::FieldEdit => match key.code {
+                KeyCode::Up | KeyCode::Char('k') => {
+                    app_state.update_field_editor(|field_editor| field_editor.previous())
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    app_state.update_field_editor(|field_editor| field_editor.next())
+                }
+                KeyCode::Enter => match &app_state.field_editor {
+                    Some(FieldEditor { field: None, .. }) => {
+                        app_state.update_field_editor(|field_editor| field_editor.pick_field())
+                    }
+                    _ => {
+                        if let Some(field_editor) = app_state.hide_field_editor() {
+                            edit_project_field(&mut app_state, field_editor);
+                        }
+                    }
                 },
+                KeyCode::Esc => {
+                    app_state.hide_field_editor();
+                }
+
+                _ => {}
+            },
+            InputMode::TemplatePick => match key.code {
+                KeyCode::Up | KeyCode::Char('k') => app_state
+                    .update_template_picker(|template_picker| template_picker.templates.previous()),
+                KeyCode::Down | KeyCode::Char('j') => app_state
+                    .update_template_picker(|template_picker| template_picker.templates.next()),
+                KeyCode::Enter => {
+                    if let Some(TemplatePicker { repo, templates }) =
+                        app_state.hide_template_picker()
+                    {
+                        if let Some(template) = templates.selected_value() {
+                            app_state.show_issue_form(IssueForm::new(repo, template.clone()));
+                        }
+                    }
+                }
+                KeyCode::Esc => {
+                    app_state.hide_template_picker();
+                }
+
+                _ => {}
+            },
+            InputMode::ProfilePick => match key.code {
+                KeyCode::Up | KeyCode::Char('k') => app_state
+                    .update_profile_picker(|profile_picker| profile_picker.profiles.previous()),
+                KeyCode::Down | KeyCode::Char('j') => {
+                    app_state.update_profile_picker(|profile_picker| profile_picker.profiles.next())
+                }
+                KeyCode::Enter => {
+                    if let Some(ProfilePicker { profiles, .. }) = app_state.hide_profile_picker() {
+                        if let Some((name, profile)) = profiles.selected_value() {
+                            switch_profile(&mut app_state, name.clone(), profile.clone());
+                        }
+                    }
+                }
+                KeyCode::Esc => {
+                    app_state.hide_profile_picker();
+                }
+
+                _ => {}
+            },
+            InputMode::IssueForm => match key.code {
+                KeyCode::Char('e') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    let Some(text) = app_state
+                        .issue_form
+                        .as_mut()
+                        .and_then(|issue_form| issue_form.selected_text().cloned())
+                    else {
+                        continue;
+                    };
+
+                    match editor::edit(terminal, text.as_str()) {
+                        Ok(edited) => app_state.update_issue_form(|issue_form| {
+                            if let Some(text) = issue_form.selected_text() {
+                                *text = edited
+                            }
+                        }),
+                        Err(err) => {
+                            app_state.hide_issue_form();
+                            app_state.show_error(format!("{} {}", Errors::EditorError, err));
+                        }
+                    }
+                }
+                KeyCode::Up => app_state.update_issue_form(|issue_form| issue_form.previous()),
+                KeyCode::Down => app_state.update_issue_form(|issue_form| issue_form.next()),
+                KeyCode::Left => app_state.update_issue_form(|issue_form| issue_form.cycle(false)),
+                KeyCode::Right => app_state.update_issue_form(|issue_form| issue_form.cycle(true)),
+                KeyCode::Char(c) => {
+                    app_state.update_issue_form(|issue_form| issue_form.type_char(c))
+                }
+                KeyCode::Backspace => {
+                    app_state.update_issue_form(|issue_form| issue_form.backspace())
+                }
+                KeyCode::Enter => {
+                    if let Some(issue_form) = app_state.hide_issue_form() {
+                        create_new_issue(&mut app_state, issue_form);
+                    }
+                }
+                KeyCode::Esc => {
+                    app_state.hide_issue_form();
+                }
+
+                _ => {}
+            },
+            InputMode::Editing => match key.code {
+                KeyCode::Enter => {
+                    let repo = Repository::from_full_name(app_state.popup.message.trim());
+                    app_state.hide_search();
+
+                    let full_name = repo.full_name.clone();
+                    app_state.move_repo_to_top(repo);
+                    load_issues(&mut app_state, full_name);
+                }
+                KeyCode::Char(c) => {
+                    app_state.popup.message.push(c);
+                }
+                KeyCode::Backspace => {
+                    app_state.popup.message.pop();
+                }
+                KeyCode::Esc => app_state.hide_search(),
+
+                _ => {}
+            },
+        }
+    }
+}
+
+/// Compose a review in the users editor and submit it on the selected pull request.
+fn review_pull_request<B: Backend>(
+    terminal: &mut Terminal<B>,
+    app_state: &mut AppState,
+    event: ReviewEvent,
+) {
+    let Some(issue) = app_state.issues.selected_value() else {
+        return;
+    };
+
+    let repo = issue.repository_name().to_string();
+    let (html_url, number) = (issue.html_url.clone(), issue.number);
+
+    let body = match editor::edit(terminal, "") {
+        Ok(body) => body,
+        Err(err) => {
+            app_state.show_error(format!("{} {}", Errors::EditorError, err));
+            return;
+        }
+    };
+
+    if body.is_empty() && event.requires_body() {
+        app_state.show_error(Errors::EmptyBodyError.to_string());
+        return;
+    }
+
+    let forge = app_state.forge.clone();
+
+    app_state.spawn_load(Load::Review(html_url, body.clone()), async move {
+        Loaded::Review(
+            forge
+                .submit_review(repo.as_str(), number, event, body)
+                .await,
+        )
+    });
+}
+
+/// Load what the current menu lists for the selected repository.
+fn load_menu(app_state: &mut AppState) {
+    match app_state.current_menu {
+        // Issues are loaded when selecting a repository
+        MenuItems::Issues => {}
+        MenuItems::Discussions => load_discussions(app_state),
+        MenuItems::Releases => load_releases(app_state),
+        MenuItems::Actions => load_runs(app_state),
+        MenuItems::Board => load_projects(app_state),
+    }
+}
+
+fn show_unsupported(app_state: &mut AppState) {
+    let backend = app_state.profile.backend;
+    app_state.show_error(format!("{} {}", Errors::UnsupportedError, backend));
+}
+
+/// Load the issues of `repo` in the background, from cache if possible.
+fn load_issues(app_state: &mut AppState, repo: String) {
+    app_state.shows_assigned = false;
+
+    if let Some(issues) = app_state.issue_cache.get(&repo) {
+        app_state.set_issues(issues.clone());
+        return;
+    }
+
+    app_state.set_issues(vec![]);
+
+    let forge = app_state.forge.clone();
+    app_state.spawn_load(Load::Issues(repo.clone()), async move {
+        Loaded::Issues(fetch_issues_with_details(forge, Some(repo)).await)
+    });
+}
+
+/// Refetch the issues of the selected repository and the assigned ones in the background.
+///
+/// With `keep_details` the checks and reviews of issues that didn't change since they were
+/// fetched are kept rather than fetched again.
+fn refresh_issues(app_state: &mut AppState, keep_details: bool) {
+    if let Some(repo) = app_state.selected_repo.as_ref() {
+        let repo = repo.full_name.clone();
+        let forge = app_state.forge.clone();
+        let known = match keep_details {
+            true => app_state
+                .issue_cache
+                .get(&repo)
+                .cloned()
+                .unwrap_or_default(),
+            false => vec![],
+        };
+
+        app_state.spawn_load(Load::Issues(repo.clone()), async move {
+            Loaded::Issues(refetch_issues(forge, Some(repo), known).await)
+        });
+    }
+
+    // Only once the user asked for them
+    if let Some(assigned) = app_state.assigned_cache.as_ref() {
+        let forge = app_state.forge.clone();
+        let known = match keep_details {
+            true => assigned.clone(),
+            false => vec![],
+        };
+
+        app_state.spawn_load(Load::IssuesSelf, async move {
+            Loaded::Issues(refetch_issues(forge, None, known).await)
+        });
+    }
+}
+
+/// Load the issues of the repository itg was launched inside of, selecting it once they arrive.
+pub fn load_origin_issues(app_state: &mut AppState, full_name: String) {
+    let forge = app_state.forge.clone();
+
+    app_state.spawn_load(Load::Origin(full_name.clone()), async move {
+        Loaded::Issues(fetch_issues_with_details(forge, Some(full_name)).await)
+    });
+}
+
+/// Fetch the issues of `repo`, or the ones assigned to the user when `None`, with their details.
+async fn fetch_issues_with_details(
+    forge: Arc<dyn Forge>,
+    repo: Option<String>,
+) -> Result<Vec<Issue>> {
+    refetch_issues(forge, repo, vec![]).await
+}
+
+/// Fetch the issues of `repo`, or the ones assigned to the user when `None`, taking the details
+/// of issues with an unchanged `updated_at` from `known` and only fetching the others.
+async fn refetch_issues(
+    forge: Arc<dyn Forge>,
+    repo: Option<String>,
+    known: Vec<Issue>,
+) -> Result<Vec<Issue>> {
+    let mut issues = match repo {
+        Some(repo) => forge.fetch_issues(repo.as_str()).await?,
+        None => forge.fetch_issues_self().await?,
+    };
+
+    let mut changed = vec![];
+
+    for issue in issues.iter_mut() {
+        let unchanged = known.iter().find(|known| {
+            known.html_url == issue.html_url
+                && !known.updated_at.is_empty()
+                && known.updated_at == issue.updated_at
+        });
+
+        match unchanged {
+            Some(known) => {
+                issue.checks = known.checks.clone();
+                issue.reviews = known.reviews.clone();
+            }
+            None => changed.push(issue.clone()),
+        }
+    }
+
+    forge.fetch_issue_details(&mut changed).await;
+
+    for detailed in changed {
+        if let Some(issue) = issues
+            .iter_mut()
+            .find(|issue| issue.html_url == detailed.html_url)
+        {
+            *issue = detailed;
+        }
+    }
+
+    Ok(issues)
+}
+
+/// Apply the result of a background fetch where it is still relevant.
+fn finish_load<B: Backend>(
+    terminal: &mut Terminal<B>,
+    app_state: &mut AppState,
+    generation: usize,
+    load: Load,
+    loaded: Loaded,
+) {
+    // Fetches started before switching profiles are dropped
+    if generation != app_state.generation || !app_state.loading.remove(&load) {
+        return;
+    }
+
+    match (load, loaded) {
+        (Load::Issues(repo), Loaded::Issues(Ok(issues))) => {
+            let shown = app_state.is_selected_repo(repo.as_str()) && !app_state.shows_assigned;
+            app_state.cache_issues(repo, issues.clone());
+
+            if shown {
+                app_state.replace_issues(issues);
+            }
+        }
+        (Load::IssuesSelf, Loaded::Issues(Ok(issues))) => {
+            app_state.cache_assigned_issues(issues.clone());
+
+            if app_state.shows_assigned {
+                app_state.replace_issues(issues);
+            }
+        }
+        (Load::Origin(full_name), Loaded::Issues(Ok(issues))) => {
+            // Unless the user picked a repository meanwhile
+            if app_state.selected_repo.is_none() && !app_state.shows_assigned {
+                let repo = app_state
+                    .repositories
+                    .items
+                    .iter()
+                    .find(|repo| repo.full_name.eq_ignore_ascii_case(full_name.as_str()))
+                    .cloned()
+                    .unwrap_or_else(|| Repository::from_full_name(full_name.as_str()));
+
+                app_state.add_repo(repo, issues);
+            } else {
+                app_state.cache_issues(full_name, issues);
+            }
+        }
+        // The clone may not be of a repository on the backend
+        (Load::Origin(_), Loaded::Issues(Err(_))) => {}
+        (Load::Readme(repo), Loaded::Readme(Ok(readme))) => {
+            app_state.readme_cache.insert(repo, readme);
+        }
+        (Load::Readme(repo), Loaded::Readme(Err(err))) => {
+            // Cache the failure so the fetch is not retried on every scroll
+            app_state.readme_cache.insert(repo, None);
+            app_state.show_error(format!("{} {}", Errors::FetchRequestError, err));
+        }
+        (Load::Repository(_), Loaded::Repository(Ok(repository))) => {
+            app_state.update_repository(*repository)
+        }
+        (Load::Comments(html_url), Loaded::Comments(Ok(comments))) => {
+            app_state.set_comments(html_url, comments)
+        }
+        (Load::Commits(html_url), Loaded::Commits(Ok(commits))) => {
+            app_state.set_commits(html_url, commits)
+        }
+        (Load::Commits(html_url), Loaded::Commits(Err(err))) => {
+            // Cache the failure so git is not run again until the issues are refreshed
+            app_state.set_commits(html_url, vec![]);
+            app_state.show_error(format!("{} {}", Errors::GitError, err));
+        }
+        (Load::Comments(html_url), Loaded::Comments(Err(err))) => {
+            // Cache the failure so the fetch is not retried on every key press
+            app_state.set_comments(html_url, vec![]);
+            app_state.show_error(format!("{} {}", Errors::FetchRequestError, err));
+        }
+        (Load::Discussions(repo), Loaded::Discussions(Ok(discussions))) => {
+            if app_state.is_selected_repo(repo.as_str()) {
+                app_state.set_discussions(repo, discussions);
+            } else {
+                app_state.discussion_cache.insert(repo, discussions);
             }
         }
+        (Load::Releases(repo), Loaded::Releases(Ok(releases))) => {
+            if app_state.is_selected_repo(repo.as_str()) {
+                app_state.set_releases(repo, releases);
+            } else {
+                app_state.release_cache.insert(repo, releases);
+            }
+        }
+        (Load::Runs(repo), Loaded::Runs(Ok(runs))) => {
+            if app_state.is_selected_repo(repo.as_str()) {
+                app_state.set_runs(runs);
+            }
+        }
+        (Load::Jobs(repo, run_id), Loaded::Jobs(Ok(jobs))) => {
+            // Only open the run if it is still selected
+            if app_state.is_selected_repo(repo.as_str())
+                && app_state.current_menu == MenuItems::Actions
+                && app_state.runs.selected_value().map(|run| run.id) == Some(run_id)
+            {
+                app_state.jobs = StatefulList::with_items(jobs);
+                app_state.screen = Screen::Jobs;
+            }
+        }
+        (Load::Projects(repo), Loaded::Projects(Ok(projects))) => {
+            if app_state.is_selected_repo(repo.as_str()) {
+                app_state.set_projects(projects);
+            }
+        }
+        (Load::PullRequest(repo, html_url), Loaded::PullRequest(Ok(pull_request))) => {
+            // Unless another dialog opened meanwhile
+            if matches!(app_state.input_mode, InputMode::Normal) {
+                app_state.show_merge(MergeDialog::new(repo, html_url, *pull_request));
+            }
+        }
+        (Load::Merge(html_url), Loaded::Merge(Ok(branch_error))) => {
+            app_state.remove_issue(html_url.as_str());
+
+            if let Some(branch_error) = branch_error {
+                app_state.show_error(branch_error);
+            }
+        }
+        // Merge errors are explained already
+        (Load::Merge(_), Loaded::Merge(Err(err))) => app_state.show_error(err.to_string()),
+        (Load::Review(html_url, _), Loaded::Review(Ok(review))) => app_state
+            .update_issue(html_url.as_str(), |issue| {
+                issue.reviews.push(review.clone())
+            }),
+        (Load::Profile(name), Loaded::Profile(result)) => {
+            // Only the latest pick is switched to
+            if app_state.profile_switch.as_ref() != Some(&name) {
+                return;
+            }
+
+            match result {
+                Ok(connection) => {
+                    let Connection {
+                        profile,
+                        forge,
+                        missing_scopes,
+                        repositories,
+                    } = *connection;
+
+                    app_state.switch_profile(name, profile, forge, repositories);
+                    app_state.show_missing_scopes(missing_scopes);
+                }
+                Err(err) => {
+                    app_state.profile_switch = None;
+                    app_state.show_error(err.to_string());
+                }
+            }
+        }
+        (Load::ProjectField(project_id, item_id), Loaded::ProjectField(Ok((field, value)))) => {
+            let Some(board) = app_state
+                .board
+                .as_mut()
+                .filter(|board| board.project.id == project_id)
+            else {
+                return;
+            };
+
+            let selected = board.selected_item().is_some_and(|item| item.id == item_id);
+            board.set_item_value(item_id.as_str(), &field, value.as_deref());
+
+            // Follow the item in case it changed columns
+            if selected {
+                board.follow_item(item_id.as_str());
+            }
+        }
+        (Load::RunUpdate(repo, _), Loaded::RunUpdate(Ok(()))) => {
+            if app_state.is_selected_repo(repo.as_str())
+                && app_state.current_menu == MenuItems::Actions
+            {
+                load_runs(app_state);
+            }
+        }
+        (Load::JobLogs(..), Loaded::JobLogs(Ok(logs))) => {
+            if let Err(err) = editor::page(terminal, logs.as_str()) {
+                app_state.show_error(format!("{} {}", Errors::PagerError, err));
+            }
+        }
+        (Load::Author(..), Loaded::Author(Ok((title, message)))) => {
+            app_state.show_message(title, message)
+        }
+        (Load::Templates(repo), Loaded::Templates(Ok(templates))) => {
+            if !matches!(app_state.input_mode, InputMode::Normal) {
+                return;
+            }
+
+            if templates.is_empty() {
+                app_state.show_issue_form(IssueForm::new(repo, IssueTemplate::blank()));
+            } else {
+                app_state.show_template_picker(TemplatePicker::new(repo, templates));
+            }
+        }
+        (Load::NewIssue(..), Loaded::NewIssue(Ok(issue))) => app_state.add_issue(*issue),
+        (Load::Comment(html_url, _), Loaded::Comment(Ok(comment))) => {
+            app_state.update_issue(html_url.as_str(), |issue| issue.comments += 1);
+
+            // Uncached comments are fetched with the new one once the issue is selected
+            if let Some(mut comments) = app_state.comment_cache.get(&html_url).cloned() {
+                comments.push(comment);
+                app_state.set_comments(html_url, comments);
+            }
+        }
+        (Load::Reply(discussion_id, reply_to, _), Loaded::Reply(Ok(comment))) => app_state
+            .update_discussion(discussion_id.as_str(), |discussion| {
+                let comments = &mut discussion.comments.nodes;
+
+                match comments
+                    .iter_mut()
+                    .find(|parent| Some(&parent.id) == reply_to.as_ref())
+                {
+                    Some(parent) => parent.replies.nodes.push(comment.clone()),
+                    None => comments.push(comment.clone()),
+                }
+            }),
+        (Load::StartWork(remote, branch), Loaded::StartWork(Ok(default_branch))) => create_branch(
+            app_state,
+            remote.as_str(),
+            default_branch.as_str(),
+            branch.as_str(),
+        ),
+        (Load::Checkout(_, number), Loaded::Checkout(Ok(branch))) => app_state.show_message(
+            String::from("Checked out"),
+            format!(
+                "Checked out pull request #{} into branch {}",
+                number, branch
+            ),
+        ),
+        (Load::Branch(remote, default_branch, branch), Loaded::Branch(Ok(()))) => app_state
+            .show_message(
+                String::from("Branch created"),
+                format!(
+                    "Checked out branch {} from {}/{}",
+                    branch, remote, default_branch
+                ),
+            ),
+        (Load::Checkout(..) | Load::Branch(..), loaded) => {
+            if let Some(err) = loaded.err() {
+                app_state.show_error(format!("{} {}", Errors::GitError, err));
+            }
+        }
+        (load, loaded) => match loaded.err() {
+            Some(err) if err.is::<Unsupported>() => show_unsupported(app_state),
+            Some(err) if load.submits() => {
+                app_state.show_error(format!("{} {}", Errors::SubmitRequestError, err))
+            }
+            Some(err) => app_state.show_error(format!("{} {}", Errors::FetchRequestError, err)),
+            None => {}
+        },
+    }
+}
+
+/// Connect with the profile `name` in the background and switch to it.
+fn switch_profile(app_state: &mut AppState, name: String, profile: Profile) {
+    if name == app_state.config.active_profile {
+        // Staying with the profile cancels a switch in flight
+        app_state.profile_switch = None;
+        return;
+    }
+
+    app_state.profile_switch = Some(name.clone());
+
+    app_state.spawn_load(Load::Profile(name.clone()), async move {
+        Loaded::Profile(connect_profile(name, profile).await.map(Box::new))
+    });
+}
+
+/// Connect with the profile `name` and fetch the repositories of its backend.
+async fn connect_profile(name: String, mut profile: Profile) -> Result<Connection> {
+    profile
+        .resolve_token()
+        .map_err(|err| anyhow!("{} {} {}", Errors::ProfileError, name, err))?;
+
+    let (forge, missing_scopes) = forge::connect_authenticated(&mut profile)
+        .await
+        .map_err(|err| anyhow!("{} {}", Errors::InvalidTokenError, err))?;
+
+    let repositories = forge::fetch_profile_repositories(forge.as_ref(), &profile)
+        .await
+        .map_err(|err| anyhow!("{} {} {}", Errors::ProfileError, name, err))?;
+
+    Ok(Connection {
+        profile,
+        forge,
+        missing_scopes,
+        repositories,
+    })
+}
+
+/// Load the README of the highlighted repository for its overview.
+fn load_overview(app_state: &mut AppState) {
+    if app_state.screen != Screen::Repositories {
+        return;
+    }
+
+    let Some(repo) = app_state.repositories.selected_value() else {
+        return;
+    };
+
+    // Both are fetched once, the first time the repository is shown
+    if app_state.readme_cache.contains_key(&repo.full_name) {
+        return;
+    }
+
+    let full_name = repo.full_name.clone();
+
+    if repo.placeholder {
+        let forge = app_state.forge.clone();
+        let full_name = full_name.clone();
+
+        app_state.spawn_load(Load::Repository(full_name.clone()), async move {
+            Loaded::Repository(
+                forge
+                    .fetch_repository(full_name.as_str())
+                    .await
+                    .map(Box::new),
+            )
+        });
+    }
+
+    let forge = app_state.forge.clone();
+
+    app_state.spawn_load(Load::Readme(full_name.clone()), async move {
+        Loaded::Readme(forge.fetch_readme(full_name.as_str()).await)
+    });
+}
+
+/// Load the projects linked to the selected repository.
+fn load_projects(app_state: &mut AppState) {
+    let Some(repo) = app_state.selected_repo.clone() else {
+        return;
+    };
+
+    let forge = app_state.forge.clone();
+
+    app_state.spawn_load(Load::Projects(repo.full_name.clone()), async move {
+        Loaded::Projects(forge.fetch_projects(repo.full_name.as_str()).await)
+    });
+}
+
+/// Move the selected project item `offset` columns to the left or right.
+fn move_project_item(app_state: &mut AppState, offset: isize) {
+    let Some(board) = &app_state.board else {
+        return;
+    };
+
+    let (Some(item), Some(status_field), Some((_, option_id))) = (
+        board.selected_item(),
+        board.status_field(),
+        board.neighbour_column(offset),
+    ) else {
+        return;
+    };
+
+    let (project_id, item_id) = (board.project.id.clone(), item.id.clone());
+    let status_field = status_field.clone();
+
+    set_project_field(app_state, project_id, item_id, status_field, option_id);
+}
+
+/// Set the field picked in the field editor to the picked option.
+fn edit_project_field(app_state: &mut AppState, field_editor: FieldEditor) {
+    let (Some(board), Some(field), Some(option)) = (
+        &app_state.board,
+        field_editor.field,
+        field_editor.options.selected_value(),
+    ) else {
+        return;
+    };
+
+    // The first option clears the field
+    let value = Some(option.id.clone()).filter(|id| !id.is_empty());
+    let project_id = board.project.id.clone();
+
+    set_project_field(app_state, project_id, field_editor.item_id, field, value);
+}
+
+/// Set `field` of the project item `item_id` to `value` in the background.
+fn set_project_field(
+    app_state: &mut AppState,
+    project_id: String,
+    item_id: String,
+    field: ProjectField,
+    value: Option<String>,
+) {
+    let forge = app_state.forge.clone();
+    let load = Load::ProjectField(project_id.clone(), item_id.clone());
+
+    app_state.spawn_load(load, async move {
+        let result = forge
+            .update_project_field(
+                project_id.as_str(),
+                item_id.as_str(),
+                &field,
+                value.as_deref(),
+            )
+            .await;
+
+        Loaded::ProjectField(result.map(|()| (field, value)))
+    });
+}
+
+/// Load the recent workflow runs of the selected repository.
+///
+/// Runs change quickly so they are never cached.
+fn load_runs(app_state: &mut AppState) {
+    let Some(repo) = app_state.selected_repo.clone() else {
+        return;
+    };
+
+    let forge = app_state.forge.clone();
+
+    app_state.spawn_load(Load::Runs(repo.full_name.clone()), async move {
+        Loaded::Runs(forge.fetch_workflow_runs(repo.full_name.as_str()).await)
+    });
+}
+
+/// Open the selected workflow run, listing its jobs.
+fn load_jobs(app_state: &mut AppState) {
+    let (Some(repo), Some(run)) = (&app_state.selected_repo, app_state.runs.selected_value())
+    else {
+        return;
+    };
+
+    let (full_name, run_id) = (repo.full_name.clone(), run.id);
+    let forge = app_state.forge.clone();
+
+    app_state.spawn_load(Load::Jobs(full_name.clone(), run_id), async move {
+        Loaded::Jobs(forge.fetch_jobs(full_name.as_str(), run_id).await)
+    });
+}
+
+enum RunUpdate {
+    RerunFailedJobs,
+    Cancel,
+}
+
+/// Re-run the failed jobs of or cancel the selected workflow run.
+fn update_run(app_state: &mut AppState, update: RunUpdate) {
+    let (Some(repo), Some(run)) = (&app_state.selected_repo, app_state.runs.selected_value())
+    else {
+        return;
+    };
+
+    let (full_name, run_id) = (repo.full_name.clone(), run.id);
+    let forge = app_state.forge.clone();
+
+    app_state.spawn_load(Load::RunUpdate(full_name.clone(), run_id), async move {
+        let repo = full_name.as_str();
+
+        Loaded::RunUpdate(match update {
+            RunUpdate::RerunFailedJobs => forge.rerun_failed_jobs(repo, run_id).await,
+            RunUpdate::Cancel => forge.cancel_workflow_run(repo, run_id).await,
+        })
+    });
+}
+
+/// Load the logs of the selected job, opening them in the users pager.
+fn load_job_logs(app_state: &mut AppState) {
+    let (Some(repo), Some(job)) = (&app_state.selected_repo, app_state.jobs.selected_value())
+    else {
+        return;
+    };
+
+    let (full_name, job_id) = (repo.full_name.clone(), job.id);
+    let forge = app_state.forge.clone();
+
+    app_state.spawn_load(Load::JobLogs(full_name.clone(), job_id), async move {
+        Loaded::JobLogs(forge.fetch_job_logs(full_name.as_str(), job_id).await)
+    });
+}
+
+/// Load the releases of the selected repository, from cache if possible.
+fn load_releases(app_state: &mut AppState) {
+    let Some(repo) = app_state.selected_repo.clone() else {
+        return;
+    };
+
+    match app_state.release_cache.get(&repo.full_name) {
+        Some(releases) => app_state.releases = StatefulList::with_items(releases.clone()),
+        None => {
+            let forge = app_state.forge.clone();
+
+            app_state.spawn_load(Load::Releases(repo.full_name.clone()), async move {
+                Loaded::Releases(forge.fetch_releases(repo.full_name.as_str()).await)
+            });
+        }
+    }
+}
+
+/// Load the discussions of the selected repository, from cache if possible.
+fn load_discussions(app_state: &mut AppState) {
+    let Some(repo) = app_state.selected_repo.clone() else {
+        return;
+    };
+
+    match app_state.discussion_cache.get(&repo.full_name) {
+        Some(discussions) => app_state.discussions = StatefulList::with_items(discussions.clone()),
+        None => {
+            let forge = app_state.forge.clone();
+
+            app_state.spawn_load(Load::Discussions(repo.full_name.clone()), async move {
+                Loaded::Discussions(forge.fetch_discussions(repo.full_name.as_str()).await)
+            });
+        }
+    }
+}
+
+/// Show the profile of the author of the selected issue or discussion with their other open issues.
+fn show_author(app_state: &mut AppState) {
+    let (login, association, repo, html_url) = match app_state.current_menu {
+        MenuItems::Discussions => {
+            let (Some(discussion), Some(repo)) = (
+                app_state.discussions.selected_value(),
+                &app_state.selected_repo,
+            ) else {
+                return;
+            };
+
+            // The focused comment's author, otherwise the discussion's
+            let author = match app_state.selected_discussion_comment() {
+                Some(comment) => (&comment.author, comment.author_association),
+                None => (&discussion.author, discussion.author_association),
+            };
+
+            match author {
+                (Some(author), association) => (
+                    author.login.clone(),
+                    association,
+                    repo.full_name.clone(),
+                    None,
+                ),
+                (None, _) => return,
+            }
+        }
+        _ => {
+            let Some(issue) = app_state.issues.selected_value() else {
+                return;
+            };
+
+            let (author, association) = match app_state.selected_issue_comment() {
+                Some(comment) => (&comment.user, comment.author_association),
+                None => (&issue.user, issue.author_association),
+            };
+
+            (
+                author.login.clone(),
+                association,
+                issue.repository_name().to_string(),
+                Some(issue.html_url.clone()),
+            )
+        }
+    };
+
+    let forge = app_state.forge.clone();
+
+    app_state.spawn_load(Load::Author(login.clone(), repo.clone()), async move {
+        Loaded::Author(fetch_author(forge.as_ref(), login, association, repo, html_url).await)
+    });
+}
+
+/// Build the title and text of the popup about the author `login`, listing their open issues in
+/// `repo` other than the one at `html_url`.
+async fn fetch_author(
+    forge: &dyn Forge,
+    login: String,
+    association: AuthorAssociation,
+    repo: String,
+    html_url: Option<String>,
+) -> Result<(String, String)> {
+    let (profile, issues) = tokio::try_join!(
+        forge.fetch_user(login.as_str()),
+        forge.fetch_issues_by(repo.as_str(), login.as_str()),
+    )?;
+
+    let mut message = format!("Association: {}\n", association);
+
+    for (label, value) in [
+        ("Name", profile.name),
+        ("Company", profile.company),
+        ("Bio", profile.bio),
+    ] {
+        if let Some(value) = value.filter(|value| !value.is_empty()) {
+            message.push_str(format!("{}: {}\n", label, value.trim()).as_str());
+        }
+    }
+
+    let other_issues = issues
+        .iter()
+        .filter(|issue| Some(&issue.html_url) != html_url.as_ref())
+        .map(|issue| format!("  {}", issue))
+        .collect::<Vec<String>>();
+
+    if other_issues.is_empty() {
+        message.push_str(format!("\nNo other open issues in {}", repo).as_str());
+    } else {
+        message.push_str(format!("\nOther open issues in {}:\n", repo).as_str());
+        message.push_str(other_issues.join("\n").as_str());
+    }
+
+    Ok((format!("@{}", profile.login), message))
+}
+
+/// Offer the issue templates of the selected repository, or an empty form when it has none.
+fn new_issue(app_state: &mut AppState) {
+    let Some(repo) = app_state
+        .selected_repo
+        .as_ref()
+        .map(|repo| repo.full_name.clone())
+    else {
+        app_state.show_error(Errors::NoRepositoryError.to_string());
+        return;
+    };
+
+    let forge = app_state.forge.clone();
+
+    app_state.spawn_load(Load::Templates(repo.clone()), async move {
+        Loaded::Templates(forge.fetch_issue_templates(repo.as_str()).await)
+    });
+}
+
+/// Create the issue written in the issue form.
+fn create_new_issue(app_state: &mut AppState, issue_form: IssueForm) {
+    let request = issue_form.request();
+
+    if request.title.is_empty() {
+        app_state.show_error(Errors::EmptyTitleError.to_string());
+        return;
+    }
+
+    let repo = issue_form.repo;
+    let forge = app_state.forge.clone();
+    let load = Load::NewIssue(repo.clone(), request.title.clone());
+
+    app_state.spawn_load(load, async move {
+        Loaded::NewIssue(
+            forge
+                .create_issue(repo.as_str(), &request)
+                .await
+                .map(Box::new),
+        )
+    });
+}
+
+/// Load the comments of the selected issue unless they are cached.
+fn load_comments(app_state: &mut AppState) {
+    if app_state.current_menu != MenuItems::Issues {
+        return;
+    }
+
+    let Some(issue) = app_state.issues.selected_value() else {
+        return;
+    };
+
+    if issue.comments == 0 || app_state.comment_cache.contains_key(&issue.html_url) {
+        return;
+    }
+
+    let issue = issue.clone();
+    let forge = app_state.forge.clone();
+
+    app_state.spawn_load(Load::Comments(issue.html_url.clone()), async move {
+        Loaded::Comments(forge.fetch_comments(&issue).await)
+    });
+}
+
+/// Look for local commits referencing the selected issue in the background, if not cached.
+fn load_commits(app_state: &mut AppState) {
+    if app_state.current_menu != MenuItems::Issues {
+        return;
+    }
+
+    let Some(issue) = app_state.issues.selected_value() else {
+        return;
+    };
+
+    if app_state.commit_cache.contains_key(&issue.html_url) {
+        return;
+    }
+
+    let repo = issue.repository_name().to_string();
+    let reference = issue.reference();
+
+    app_state.spawn_load(Load::Commits(issue.html_url.clone()), async move {
+        let commits = tokio::task::spawn_blocking(move || {
+            // Only look for commits when running inside a clone of the issues repository
+            match git::find_remote(repo.as_str()) {
+                Some(_) => git::referencing_commits(reference.as_str()),
+                None => Ok(vec![]),
+            }
+        })
+        .await;
+
+        Loaded::Commits(commits.map_err(Into::into).and_then(|commits| commits))
+    });
+}
+
+/// Compose a comment in the users editor and post it on the selected issue.
+fn comment_on_issue<B: Backend>(terminal: &mut Terminal<B>, app_state: &mut AppState) {
+    let Some(issue) = app_state.issues.selected_value().cloned() else {
+        return;
+    };
+
+    let body = match editor::edit(terminal, "") {
+        Ok(body) if body.is_empty() => {
+            app_state.show_error(Errors::EmptyBodyError.to_string());
+            return;
+        }
+        Ok(body) => body,
+        Err(err) => {
+            app_state.show_error(format!("{} {}", Errors::EditorError, err));
+            return;
+        }
+    };
+
+    let forge = app_state.forge.clone();
+    let load = Load::Comment(issue.html_url.clone(), body.clone());
+
+    app_state.spawn_load(load, async move {
+        Loaded::Comment(forge.create_comment(&issue, body.as_str()).await)
+    });
+}
+
+/// Compose a reply in the users editor and post it on the selected discussion.
+fn reply_to_discussion<B: Backend>(terminal: &mut Terminal<B>, app_state: &mut AppState) {
+    let Some(discussion_id) = app_state
+        .discussions
+        .selected_value()
+        .map(|discussion| discussion.id.clone())
+    else {
+        return;
+    };
+
+    // Thread the reply under the focused comment
+    let reply_to = app_state
+        .selected_discussion_comment()
+        .map(|comment| comment.id.clone());
+
+    let body = match editor::edit(terminal, "") {
+        Ok(body) if body.is_empty() => {
+            app_state.show_error(Errors::EmptyBodyError.to_string());
+            return;
+        }
+        Ok(body) => body,
+        Err(err) => {
+            app_state.show_error(format!("{} {}", Errors::EditorError, err));
+            return;
+        }
+    };
+
+    let forge = app_state.forge.clone();
+    let load = Load::Reply(discussion_id.clone(), reply_to.clone(), body.clone());
+
+    app_state.spawn_load(load, async move {
+        Loaded::Reply(
+            forge
+                .reply_discussion(discussion_id.as_str(), reply_to.as_deref(), body)
+                .await,
+        )
+    });
+}
+
+/// Merge a pull request as prepared in the merge dialog.
+fn merge(app_state: &mut AppState, merge_dialog: MergeDialog) {
+    let forge = app_state.forge.clone();
+
+    app_state.spawn_load(Load::Merge(merge_dialog.html_url.clone()), async move {
+        Loaded::Merge(merge_pull_request(forge.as_ref(), merge_dialog).await)
+    });
+}
+
+/// Merge the pull request of `merge_dialog`, deleting its branch if asked to.
+///
+/// Returns why the branch could not be deleted, if it couldn't.
+async fn merge_pull_request(
+    forge: &dyn Forge,
+    merge_dialog: MergeDialog,
+) -> Result<Option<String>> {
+    let pull_request = &merge_dialog.pull_request;
+
+    match forge
+        .merge_pull_request(
+            merge_dialog.repo.as_str(),
+            pull_request.number,
+            &merge_dialog.request(),
+        )
+        .await
+    {
+        Ok(result) if result.merged => {}
+        Ok(result) => bail!(result.message),
+        Err(err) => match err.downcast_ref::<RequestError>() {
+            Some(error) if error.status == StatusCode::METHOD_NOT_ALLOWED => {
+                if pull_request.mergeable == Some(false) || pull_request.mergeable_state == "dirty"
+                {
+                    bail!(Errors::MergeConflictError.to_string());
+                }
+
+                bail!("{} {}", Errors::MergeBlockedError, error.message);
+            }
+            Some(error) if error.status == StatusCode::CONFLICT => {
+                bail!(Errors::HeadModifiedError.to_string())
+            }
+            _ => bail!("{} {}", Errors::SubmitRequestError, err),
+        },
+    }
+
+    if !merge_dialog.delete_branch {
+        return Ok(None);
+    }
+
+    let Some(head_repo) = &pull_request.head.repo else {
+        return Ok(Some(format!(
+            "{} the head repository no longer exists.",
+            Errors::DeleteBranchError
+        )));
+    };
+
+    let deleted = forge
+        .delete_branch(
+            head_repo.full_name.as_str(),
+            pull_request.head.branch.as_str(),
+        )
+        .await;
+
+    Ok(deleted
+        .err()
+        .map(|err| format!("{} {}", Errors::DeleteBranchError, err)))
+}
+
+/// Check out a pull request in the local clone of `repo`.
+fn checkout_pull_request(app_state: &mut AppState, repo: &str, number: usize) {
+    let Some(remote) = clean_clone_remote(app_state, repo) else {
+        return;
+    };
+
+    // Fetching the pull request goes over the network
+    app_state.spawn_load(Load::Checkout(remote.clone(), number), async move {
+        let branch = tokio::task::spawn_blocking(move || {
+            git::checkout_pull_request(remote.as_str(), number)
+        })
+        .await;
+
+        Loaded::Checkout(branch.map_err(Into::into).and_then(|branch| branch))
+    });
+}
+
+/// Create and check out a branch for the selected issue from the default branch of its repository.
+fn start_work(app_state: &mut AppState) {
+    let Some(issue) = app_state.issues.selected_value().cloned() else {
+        return;
+    };
+
+    let repo = issue.repository_name().to_string();
+
+    let Some(remote) = clean_clone_remote(app_state, repo.as_str()) else {
+        return;
+    };
+
+    let branch = git::branch_name(
+        app_state.config.branch_template.as_str(),
+        issue.number,
+        issue.title.as_str(),
+    );
+
+    let default_branch = app_state
+        .repositories
+        .items
+        .iter()
+        .find(|repository| repository.full_name == repo && !repository.default_branch.is_empty())
+        .map(|repository| repository.default_branch.clone());
+
+    match default_branch {
+        Some(default_branch) => create_branch(
+            app_state,
+            remote.as_str(),
+            default_branch.as_str(),
+            branch.as_str(),
+        ),
+        None => {
+            let forge = app_state.forge.clone();
+
+            app_state.spawn_load(Load::StartWork(remote, branch), async move {
+                Loaded::StartWork(
+                    forge
+                        .fetch_repository(repo.as_str())
+                        .await
+                        .map(|repository| repository.default_branch),
+                )
+            });
+        }
+    }
+}
+
+/// Create and check out `branch` from `default_branch` of `remote`.
+fn create_branch(app_state: &mut AppState, remote: &str, default_branch: &str, branch: &str) {
+    let (remote, default_branch, branch) = (
+        remote.to_string(),
+        default_branch.to_string(),
+        branch.to_string(),
+    );
+    let load = Load::Branch(remote.clone(), default_branch.clone(), branch.clone());
+
+    // Fetching the default branch goes over the network
+    app_state.spawn_load(load, async move {
+        let created = tokio::task::spawn_blocking(move || {
+            git::create_branch(remote.as_str(), default_branch.as_str(), branch.as_str())
+        })
+        .await;
+
+        Loaded::Branch(created.map_err(Into::into).and_then(|created| created))
+    });
+}
+
+/// Find the remote of the local clone of `repo`, making sure the working tree is clean.
+///
+/// Shows an error and returns `None` when it is not.
+fn clean_clone_remote(app_state: &mut AppState, repo: &str) -> Option<String> {
+    let Some(remote) = git::find_remote(repo) else {
+        app_state.show_error(format!("{} {}", Errors::NotInRepositoryError, repo));
+        return None;
+    };
+
+    match git::is_dirty() {
+        Ok(false) => Some(remote),
+        Ok(true) => {
+            app_state.show_error(Errors::DirtyWorktreeError.to_string());
+            None
+        }
+        Err(err) => {
+            app_state.show_error(format!("{} {}", Errors::GitError, err));
+            None
+        }
+    }
+}
+
+fn open_in_browser(app_state: &mut AppState, url: &str) {
+    if let Err(err) = webbrowser::open(url) {
+        app_state.show_error(format!("{} {}", Errors::BrowserError, err));
     }
 }
@@ -0,0 +1,72 @@
+use anyhow::{bail, Result};
+use crossterm::terminal::{enable_raw_mode, EnterAlternateScreen};
+use std::{env, fs, io, io::Write, process::Command};
+use tui::{backend::Backend, Terminal};
+
+use crate::{events, reset_terminal};
+
+/// Open `initial` in the users editor and return the edited text.
+///
+/// The terminal is handed over to the editor and restored once it exits.
+pub fn edit<B: Backend>(terminal: &mut Terminal<B>, initial: &str) -> Result<String> {
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or(String::from("vi"));
+
+    suspend(terminal, || open_file(editor.as_str(), "md", initial))
+        .map(|content| content.trim().to_string())
+}
+
+/// Show `content` in the users pager.
+pub fn page<B: Backend>(terminal: &mut Terminal<B>, content: &str) -> Result<()> {
+    let pager = env::var("PAGER").unwrap_or(String::from("less"));
+
+    suspend(terminal, || open_file(pager.as_str(), "log", content)).map(|_| ())
+}
+
+/// Hand the terminal over to `run` and restore it afterwards.
+fn suspend<B: Backend, T>(
+    terminal: &mut Terminal<B>,
+    run: impl FnOnce() -> Result<T>,
+) -> Result<T> {
+    reset_terminal()?;
+    events::pause_input(true);
+
+    let result = run();
+
+    events::pause_input(false);
+
+    crossterm::execute!(io::stdout(), EnterAlternateScreen)?;
+    enable_raw_mode()?;
+    terminal.clear()?;
+
+    result
+}
+
+/// Open `content` in `program` through a temporary file and return the file afterwards.
+fn open_file(program: &str, extension: &str, content: &str) -> Result<String> {
+    // Created with a random name so other users of the temp directory can't plant it beforehand
+    let mut file = tempfile::Builder::new()
+        .prefix("itg-")
+        .suffix(format!(".{}", extension).as_str())
+        .tempfile()?;
+    file.write_all(content.as_bytes())?;
+
+    let path = file.path();
+
+    // The program may be set with arguments, e.g. `code --wait`
+    let mut args = program.split_whitespace();
+    let status = Command::new(args.next().unwrap_or(program))
+        .args(args)
+        .arg(path)
+        .status();
+
+    // Editors may replace the file, so it is read back by path. It is removed once dropped.
+    let content = fs::read_to_string(path);
+
+    if !status?.success() {
+        bail!("{} exited with an error", program);
+    }
+
+    Ok(content?)
+}
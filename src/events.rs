@@ -0,0 +1,270 @@
+use anyhow::Result;
+use crossterm::event::{self, Event, KeyEvent};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+use tokio::{
+    sync::mpsc::{self, UnboundedReceiver, UnboundedSender},
+    time,
+};
+
+use crate::{
+    forge::Forge,
+    models::{
+        comment::Comment,
+        commit::Commit,
+        config::Profile,
+        discussion::{Discussion, DiscussionComment},
+        issue::Issue,
+        issue_template::IssueTemplate,
+        project::{Project, ProjectField},
+        pull_request::PullRequest,
+        release::Release,
+        repository::Repository,
+        review::Review,
+        workflow_run::{Job, WorkflowRun},
+    },
+};
+
+/// How often the input thread posts a tick when no key is pressed, animating loading spinners.
+const TICK_RATE: Duration = Duration::from_millis(120);
+
+/// Set while an editor or pager owns the terminal so the input thread leaves its keys alone.
+static INPUT_PAUSED: AtomicBool = AtomicBool::new(false);
+
+pub fn pause_input(paused: bool) {
+    INPUT_PAUSED.store(paused, Ordering::SeqCst);
+}
+
+/// Something the app loop reacts to.
+pub enum AppEvent {
+    Key(KeyEvent),
+    Tick,
+    /// Time to refetch the listed issues
+    Refresh,
+    /// A background fetch finished, started with the profile of the generation
+    Loaded(usize, Load, Loaded),
+}
+
+/// A background fetch or request, identified by what it loads so duplicate requests are
+/// coalesced.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub enum Load {
+    /// The issues of a repository
+    Issues(String),
+    /// The issues assigned to the user
+    IssuesSelf,
+    /// The issues of the repository itg was launched inside of, to preselect it
+    Origin(String),
+    Readme(String),
+    /// The details of a repository only known by name
+    Repository(String),
+    /// The comments of the issue with the url
+    Comments(String),
+    /// The local commits referencing the issue with the url
+    Commits(String),
+    Discussions(String),
+    Releases(String),
+    Runs(String),
+    /// The jobs of a workflow run in a repository
+    Jobs(String, u64),
+    Projects(String),
+    /// The pull request of a repository with the url, to merge it
+    PullRequest(String, String),
+    /// Merging the pull request with the url
+    Merge(String),
+    /// Reviewing the pull request with the url with the body
+    Review(String, String),
+    /// Connecting with the profile of the name
+    Profile(String),
+    /// Setting a field of an item of a project
+    ProjectField(String, String),
+    /// Re-running or cancelling a workflow run in a repository
+    RunUpdate(String, u64),
+    /// The logs of a job in a repository
+    JobLogs(String, u64),
+    /// The profile of the user with the login and their issues in a repository
+    Author(String, String),
+    /// The issue templates of a repository, to write a new issue
+    Templates(String),
+    /// Creating an issue in a repository with the title
+    NewIssue(String, String),
+    /// Commenting on the issue with the url with the body
+    Comment(String, String),
+    /// Replying to a discussion, under a comment if given, with the body
+    Reply(String, Option<String>, String),
+    /// The default branch of a repository, to create a branch from on the remote
+    StartWork(String, String),
+    /// Checking out the pull request with the number from a remote
+    Checkout(String, usize),
+    /// Creating a branch from the default branch of a remote, as remote, default and new branch
+    Branch(String, String, String),
+}
+
+impl Load {
+    /// Whether the load submits something rather than only fetching it.
+    pub fn submits(&self) -> bool {
+        matches!(
+            self,
+            Load::Merge(_)
+                | Load::Review(..)
+                | Load::ProjectField(..)
+                | Load::RunUpdate(..)
+                | Load::NewIssue(..)
+                | Load::Comment(..)
+                | Load::Reply(..)
+        )
+    }
+}
+
+/// A backend connected with a profile, ready to switch to.
+pub struct Connection {
+    pub profile: Profile,
+    pub forge: Arc<dyn Forge>,
+    pub missing_scopes: Vec<String>,
+    pub repositories: Vec<Repository>,
+}
+
+/// The result of a background fetch.
+pub enum Loaded {
+    Issues(Result<Vec<Issue>>),
+    Readme(Result<Option<String>>),
+    /// Boxed as repositories are much larger than the other results
+    Repository(Result<Box<Repository>>),
+    Comments(Result<Vec<Comment>>),
+    Commits(Result<Vec<Commit>>),
+    Discussions(Result<Vec<Discussion>>),
+    Releases(Result<Vec<Release>>),
+    Runs(Result<Vec<WorkflowRun>>),
+    Jobs(Result<Vec<Job>>),
+    Projects(Result<Vec<Project>>),
+    PullRequest(Result<Box<PullRequest>>),
+    /// Why the head branch could not be deleted after merging, if it couldn't
+    Merge(Result<Option<String>>),
+    Review(Result<Review>),
+    Profile(Result<Box<Connection>>),
+    /// The field and value that were set
+    ProjectField(Result<(ProjectField, Option<String>)>),
+    RunUpdate(Result<()>),
+    JobLogs(Result<String>),
+    /// The title and text of the author popup
+    Author(Result<(String, String)>),
+    Templates(Result<Vec<IssueTemplate>>),
+    NewIssue(Result<Box<Issue>>),
+    Comment(Result<Comment>),
+    Reply(Result<DiscussionComment>),
+    StartWork(Result<String>),
+    /// The local branch the pull request was checked out into
+    Checkout(Result<String>),
+    Branch(Result<()>),
+}
+
+impl Loaded {
+    /// The error of a failed fetch.
+    pub fn err(self) -> Option<anyhow::Error> {
+        match self {
+            Loaded::Issues(result) => result.err(),
+            Loaded::Readme(result) => result.err(),
+            Loaded::Repository(result) => result.err(),
+            Loaded::Comments(result) => result.err(),
+            Loaded::Commits(result) => result.err(),
+            Loaded::Discussions(result) => result.err(),
+            Loaded::Releases(result) => result.err(),
+            Loaded::Runs(result) => result.err(),
+            Loaded::Jobs(result) => result.err(),
+            Loaded::Projects(result) => result.err(),
+            Loaded::PullRequest(result) => result.err(),
+            Loaded::Merge(result) => result.err(),
+            Loaded::Review(result) => result.err(),
+            Loaded::Profile(result) => result.err(),
+            Loaded::ProjectField(result) => result.err(),
+            Loaded::RunUpdate(result) => result.err(),
+            Loaded::JobLogs(result) => result.err(),
+            Loaded::Author(result) => result.err(),
+            Loaded::Templates(result) => result.err(),
+            Loaded::NewIssue(result) => result.err(),
+            Loaded::Comment(result) => result.err(),
+            Loaded::Reply(result) => result.err(),
+            Loaded::StartWork(result) => result.err(),
+            Loaded::Checkout(result) => result.err(),
+            Loaded::Branch(result) => result.err(),
+        }
+    }
+}
+
+/// The channel of app events, fed by an input thread and background fetches.
+pub struct Events {
+    sender: UnboundedSender<AppEvent>,
+    receiver: UnboundedReceiver<AppEvent>,
+}
+
+impl Events {
+    /// Start reading keys on a separate thread.
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let input = sender.clone();
+
+        thread::spawn(move || loop {
+            if INPUT_PAUSED.load(Ordering::SeqCst) {
+                thread::sleep(TICK_RATE);
+                continue;
+            }
+
+            let event = match event::poll(TICK_RATE) {
+                // Check again as the editor may have opened while polling
+                Ok(true) if !INPUT_PAUSED.load(Ordering::SeqCst) => match event::read() {
+                    Ok(Event::Key(key)) => AppEvent::Key(key),
+                    _ => continue,
+                },
+                Ok(_) => AppEvent::Tick,
+                Err(_) => break,
+            };
+
+            // The app closed the channel on exit
+            if input.send(event).is_err() {
+                break;
+            }
+        });
+
+        Self { sender, receiver }
+    }
+
+    /// A sender for background fetches to post their results with.
+    pub fn sender(&self) -> UnboundedSender<AppEvent> {
+        self.sender.clone()
+    }
+
+    /// Post a refresh every `period`.
+    pub fn refresh_every(&self, period: Duration) {
+        let sender = self.sender.clone();
+
+        tokio::spawn(async move {
+            let mut interval = time::interval(period);
+            // The first tick completes immediately
+            interval.tick().await;
+
+            loop {
+                interval.tick().await;
+
+                if sender.send(AppEvent::Refresh).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    pub async fn next(&mut self) -> Option<AppEvent> {
+        self.receiver.recv().await
+    }
+}
+
+impl Default for Events {
+    fn default() -> Self {
+        Self::new()
+    }
+}
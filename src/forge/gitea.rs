@@ -0,0 +1,483 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use reqwest::{Method, RequestBuilder, StatusCode};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::json;
+
+use super::Forge;
+use crate::{
+    api_requests::check_response,
+    models::{
+        comment::Comment,
+        issue::{Issue, IssuePullRequest, Label, Milestone, NewIssue},
+        issue_template::{FormElement, IssueTemplate, TemplateBody},
+        repository::Repository,
+        user::{AuthenticatedUser, User, UserProfile},
+    },
+};
+
+/// Items asked for per page, the most Gitea instances hand out by default.
+const PAGE_LIMIT: usize = 50;
+
+/// Pages fetched of a list at most, so huge repositories don't take minutes to load.
+const MAX_PAGES: usize = 10;
+
+/// The REST API (v1) of a Gitea or Forgejo instance.
+pub struct Gitea {
+    /// Url of the instance, without a trailing slash
+    url: String,
+    token: String,
+    client: reqwest::Client,
+}
+
+#[derive(Deserialize)]
+struct GiteaRepository {
+    name: String,
+    full_name: String,
+    #[serde(default)]
+    open_issues_count: isize,
+    #[serde(default)]
+    default_branch: String,
+    description: Option<String>,
+    #[serde(default)]
+    topics: Vec<String>,
+    #[serde(default)]
+    stars_count: usize,
+    #[serde(default)]
+    forks_count: usize,
+    updated_at: Option<String>,
+}
+
+impl From<GiteaRepository> for Repository {
+    fn from(repository: GiteaRepository) -> Self {
+        Self {
+            name: repository.name,
+            full_name: repository.full_name,
+            open_issues_count: repository.open_issues_count,
+            default_branch: repository.default_branch,
+            description: repository.description,
+            topics: repository.topics,
+            stargazers_count: repository.stars_count,
+            forks_count: repository.forks_count,
+            license: None,
+            pushed_at: repository.updated_at,
+            placeholder: false,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct GiteaIssue {
+    number: usize,
+    title: String,
+    #[serde(default)]
+    body: String,
+    html_url: String,
+    user: User,
+    #[serde(default)]
+    comments: usize,
+    #[serde(default)]
+    labels: Vec<Label>,
+    milestone: Option<Milestone>,
+    #[serde(default)]
+    updated_at: String,
+    /// Only present for pull requests
+    pull_request: Option<serde_json::Value>,
+    repository: IssueRepository,
+}
+
+#[derive(Deserialize)]
+struct IssueRepository {
+    full_name: String,
+}
+
+#[derive(Deserialize)]
+struct GiteaUser {
+    login: String,
+    full_name: Option<String>,
+    description: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GiteaLabel {
+    id: u64,
+    name: String,
+}
+
+/// An issue template, parsed by Gitea.
+#[derive(Deserialize)]
+struct GiteaTemplate {
+    name: String,
+    #[serde(default)]
+    about: String,
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    labels: Vec<String>,
+    #[serde(default)]
+    assignees: Vec<String>,
+    #[serde(default)]
+    content: String,
+    /// The fields of issue forms
+    body: Option<Vec<FormElement>>,
+}
+
+impl From<GiteaTemplate> for IssueTemplate {
+    fn from(template: GiteaTemplate) -> Self {
+        Self {
+            name: template.name,
+            about: template.about,
+            title: template.title,
+            labels: template.labels,
+            assignees: template.assignees,
+            body: match template.body {
+                Some(form) if !form.is_empty() => TemplateBody::Form(form),
+                _ => TemplateBody::Markdown(template.content),
+            },
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct NewGiteaIssue<'a> {
+    title: &'a str,
+    body: &'a str,
+    assignees: &'a [String],
+    /// Label ids
+    labels: Vec<u64>,
+}
+
+impl Gitea {
+    pub fn new(url: &str, token: &str) -> Self {
+        Self {
+            url: url.trim_end_matches('/').to_string(),
+            token: token.to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.client
+            .request(method, format!("{}/api/v1/{}", self.url, path))
+            .header("Authorization", format!("token {}", self.token))
+    }
+
+    async fn fetch<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let raw = self.request(Method::GET, path).send().await?;
+
+        Ok(check_response(raw).await?.json::<T>().await?)
+    }
+
+    async fn send<B: Serialize + Sync, T: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> Result<T> {
+        let raw = self.request(Method::POST, path).json(body).send().await?;
+
+        Ok(check_response(raw).await?.json::<T>().await?)
+    }
+
+    /// Fetch the list at `path` page by page, until a page comes back short.
+    async fn fetch_pages<T: DeserializeOwned>(&self, path: &str) -> Result<Vec<T>> {
+        let separator = if path.contains('?') { '&' } else { '?' };
+        let mut items = vec![];
+
+        for page in 1..=MAX_PAGES {
+            let mut batch = self
+                .fetch::<Vec<T>>(
+                    format!("{}{}limit={}&page={}", path, separator, PAGE_LIMIT, page).as_str(),
+                )
+                .await?;
+            let last = batch.len() < PAGE_LIMIT;
+
+            items.append(&mut batch);
+
+            if last {
+                break;
+            }
+        }
+
+        Ok(items)
+    }
+
+    fn to_issue(&self, issue: GiteaIssue) -> Issue {
+        Issue {
+            pull_request: issue.pull_request.map(|_| IssuePullRequest {
+                html_url: issue.html_url.clone(),
+            }),
+            html_url: issue.html_url,
+            repository: issue.repository.full_name,
+            number: issue.number,
+            // Pull requests are numbered along with issues
+            sigil: '#',
+            title: issue.title,
+            body: issue.body,
+            user: issue.user,
+            author_association: Default::default(),
+            comments: issue.comments,
+            labels: issue.labels,
+            milestone: issue.milestone,
+            updated_at: issue.updated_at,
+            checks: None,
+            reviews: vec![],
+        }
+    }
+
+    async fn fetch_issue_list(&self, path: &str) -> Result<Vec<Issue>> {
+        Ok(self
+            .fetch_pages::<GiteaIssue>(path)
+            .await?
+            .into_iter()
+            .map(|issue| self.to_issue(issue))
+            .collect())
+    }
+}
+
+#[async_trait]
+impl Forge for Gitea {
+    async fn fetch_repositories(&self) -> Result<Vec<Repository>> {
+        Ok(self
+            .fetch_pages::<GiteaRepository>("user/repos")
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+
+    async fn fetch_repository(&self, repo: &str) -> Result<Repository> {
+        Ok(self
+            .fetch::<GiteaRepository>(format!("repos/{}", repo).as_str())
+            .await?
+            .into())
+    }
+
+    async fn fetch_issues_self(&self) -> Result<Vec<Issue>> {
+        self.fetch_issue_list("repos/issues/search?state=open&assigned=true")
+            .await
+    }
+
+    async fn fetch_issues(&self, repo: &str) -> Result<Vec<Issue>> {
+        self.fetch_issue_list(format!("repos/{}/issues?state=open", repo).as_str())
+            .await
+    }
+
+    async fn fetch_issue(&self, issue: &Issue) -> Result<Issue> {
+        let updated = self
+            .fetch::<GiteaIssue>(
+                format!("repos/{}/issues/{}", issue.repository_name(), issue.number).as_str(),
+            )
+            .await?;
+
+        Ok(self.to_issue(updated))
+    }
+
+    async fn fetch_issues_by(&self, repo: &str, login: &str) -> Result<Vec<Issue>> {
+        self.fetch_issue_list(
+            format!(
+                "repos/{}/issues?state=open&type=issues&created_by={}",
+                repo, login
+            )
+            .as_str(),
+        )
+        .await
+    }
+
+    async fn fetch_comments(&self, issue: &Issue) -> Result<Vec<Comment>> {
+        self.fetch::<Vec<Comment>>(
+            format!(
+                "repos/{}/issues/{}/comments",
+                issue.repository_name(),
+                issue.number
+            )
+            .as_str(),
+        )
+        .await
+    }
+
+    async fn fetch_user(&self, login: &str) -> Result<UserProfile> {
+        let user = self
+            .fetch::<GiteaUser>(format!("users/{}", login).as_str())
+            .await?;
+
+        Ok(UserProfile {
+            login: user.login,
+            name: user.full_name,
+            company: None,
+            bio: user.description,
+        })
+    }
+
+    async fn fetch_authenticated_user(&self) -> Result<AuthenticatedUser> {
+        let user = self.fetch::<GiteaUser>("user").await?;
+
+        Ok(AuthenticatedUser {
+            login: user.login,
+            scopes: None,
+        })
+    }
+
+    async fn fetch_readme(&self, repo: &str) -> Result<Option<String>> {
+        let raw = self
+            .request(
+                Method::GET,
+                format!("repos/{}/raw/README.md", repo).as_str(),
+            )
+            .send()
+            .await?;
+
+        if raw.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        Ok(Some(check_response(raw).await?.text().await?))
+    }
+
+    async fn fetch_issue_templates(&self, repo: &str) -> Result<Vec<IssueTemplate>> {
+        Ok(self
+            .fetch::<Vec<GiteaTemplate>>(format!("repos/{}/issue_templates", repo).as_str())
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+
+    async fn create_issue(&self, repo: &str, issue: &NewIssue) -> Result<Issue> {
+        // Gitea labels issues by id
+        let labels = if issue.labels.is_empty() {
+            vec![]
+        } else {
+            self.fetch::<Vec<GiteaLabel>>(format!("repos/{}/labels", repo).as_str())
+                .await?
+                .into_iter()
+                .filter(|label| issue.labels.contains(&label.name))
+                .map(|label| label.id)
+                .collect()
+        };
+
+        let created = self
+            .send::<NewGiteaIssue, GiteaIssue>(
+                format!("repos/{}/issues", repo).as_str(),
+                &NewGiteaIssue {
+                    title: issue.title.as_str(),
+                    body: issue.body.as_str(),
+                    assignees: issue.assignees.as_slice(),
+                    labels,
+                },
+            )
+            .await?;
+
+        Ok(self.to_issue(created))
+    }
+
+    async fn create_comment(&self, issue: &Issue, body: &str) -> Result<Comment> {
+        self.send(
+            format!(
+                "repos/{}/issues/{}/comments",
+                issue.repository_name(),
+                issue.number
+            )
+            .as_str(),
+            &json!({ "body": body }),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{api_requests::RequestError, test_server::serve};
+    use serde_json::Value;
+
+    const NOT_FOUND: &str = "The target couldn't be found.";
+
+    fn gitea_issue(number: usize, pull_request: bool) -> Value {
+        json!({
+            "number": number,
+            "title": format!("Title of #{}", number),
+            "body": "",
+            "html_url": format!("https://gitea.example.com/owner/repo/issues/{}", number),
+            "user": { "login": "alice" },
+            "comments": 2,
+            "labels": [{ "name": "bug" }],
+            "milestone": { "title": "v1.0" },
+            "updated_at": "2023-01-01T00:00:00Z",
+            "pull_request": if pull_request { json!({ "merged": false }) } else { Value::Null },
+            "repository": { "full_name": "owner/repo" },
+        })
+    }
+
+    #[tokio::test]
+    async fn lists_issues_page_by_page() {
+        let first_page = (1..=PAGE_LIMIT)
+            .map(|number| gitea_issue(number, false))
+            .collect::<Vec<Value>>();
+
+        let url = serve(
+            vec![
+                (
+                    "/api/v1/repos/owner/repo/issues?state=open&limit=50&page=1",
+                    json!(first_page),
+                ),
+                (
+                    "/api/v1/repos/owner/repo/issues?state=open&limit=50&page=2",
+                    json!([gitea_issue(51, true)]),
+                ),
+            ],
+            NOT_FOUND,
+        )
+        .await;
+
+        let issues = Gitea::new(url.as_str(), "token")
+            .fetch_issues("owner/repo")
+            .await
+            .unwrap();
+
+        assert_eq!(issues.len(), 51);
+        assert_eq!(issues[0].to_string(), "1: Title of #1");
+        assert_eq!(issues[50].reference(), "#51");
+    }
+
+    #[tokio::test]
+    async fn maps_gitea_issues_and_pull_requests() {
+        let url = serve(
+            vec![(
+                "/api/v1/repos/issues/search?state=open&assigned=true&limit=50&page=1",
+                json!([gitea_issue(1, false), gitea_issue(2, true)]),
+            )],
+            NOT_FOUND,
+        )
+        .await;
+
+        let issues = Gitea::new(url.as_str(), "token")
+            .fetch_issues_self()
+            .await
+            .unwrap();
+
+        assert_eq!(issues.len(), 2);
+        assert!(!issues[0].is_pull_request());
+        assert!(issues[1].is_pull_request());
+
+        let issue = &issues[0];
+
+        assert_eq!(issue.repository_name(), "owner/repo");
+        assert_eq!(issue.user.login, "alice");
+        assert_eq!(issue.comments, 2);
+        assert_eq!(issue.labels[0].name, "bug");
+        assert_eq!(issue.milestone.as_ref().unwrap().title, "v1.0");
+        assert_eq!(issue.updated_at, "2023-01-01T00:00:00Z");
+    }
+
+    #[tokio::test]
+    async fn surfaces_gitea_error_messages() {
+        let url = serve(vec![], NOT_FOUND).await;
+        let gitea = Gitea::new(url.as_str(), "token");
+
+        let err = gitea.fetch_issues("owner/missing").await.err().unwrap();
+        let err = err.downcast_ref::<RequestError>().unwrap();
+
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+        assert_eq!(err.message, NOT_FOUND);
+    }
+}
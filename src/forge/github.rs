@@ -0,0 +1,170 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+use super::Forge;
+use crate::{
+    api_requests,
+    models::{
+        comment::Comment,
+        config::Profile,
+        discussion::{Discussion, DiscussionComment},
+        issue::{Issue, NewIssue},
+        issue_template::IssueTemplate,
+        project::{Project, ProjectField},
+        pull_request::{MergeRequest, MergeResult, PullRequest},
+        release::Release,
+        repository::Repository,
+        review::{Review, ReviewEvent},
+        user::{AuthenticatedUser, UserProfile},
+        workflow_run::{Job, WorkflowRun},
+    },
+};
+
+/// The Github REST API.
+pub struct Github {
+    profile: Profile,
+}
+
+impl Github {
+    pub fn new(profile: Profile) -> Self {
+        Self { profile }
+    }
+}
+
+#[async_trait]
+impl Forge for Github {
+    async fn fetch_repositories(&self) -> Result<Vec<Repository>> {
+        api_requests::fetch_repositories(&self.profile).await
+    }
+
+    async fn fetch_repository(&self, repo: &str) -> Result<Repository> {
+        api_requests::fetch_repository(&self.profile, repo).await
+    }
+
+    async fn fetch_issues_self(&self) -> Result<Vec<Issue>> {
+        api_requests::fetch_issues_self(&self.profile).await
+    }
+
+    async fn fetch_issues(&self, repo: &str) -> Result<Vec<Issue>> {
+        api_requests::fetch_issues_repo(&self.profile, repo).await
+    }
+
+    async fn fetch_issue(&self, issue: &Issue) -> Result<Issue> {
+        api_requests::fetch_issue(&self.profile, issue.repository_name(), issue.number).await
+    }
+
+    async fn fetch_issues_by(&self, repo: &str, login: &str) -> Result<Vec<Issue>> {
+        api_requests::fetch_issues_by(&self.profile, repo, login).await
+    }
+
+    async fn fetch_comments(&self, issue: &Issue) -> Result<Vec<Comment>> {
+        api_requests::fetch_comments(&self.profile, issue.repository_name(), issue.number).await
+    }
+
+    async fn fetch_user(&self, login: &str) -> Result<UserProfile> {
+        api_requests::fetch_user(&self.profile, login).await
+    }
+
+    async fn fetch_authenticated_user(&self) -> Result<AuthenticatedUser> {
+        api_requests::fetch_authenticated_user(&self.profile).await
+    }
+
+    async fn fetch_readme(&self, repo: &str) -> Result<Option<String>> {
+        api_requests::fetch_readme(&self.profile, repo).await
+    }
+
+    async fn fetch_issue_templates(&self, repo: &str) -> Result<Vec<IssueTemplate>> {
+        api_requests::fetch_issue_templates(&self.profile, repo).await
+    }
+
+    async fn create_issue(&self, repo: &str, issue: &NewIssue) -> Result<Issue> {
+        api_requests::create_issue(&self.profile, repo, issue).await
+    }
+
+    async fn create_comment(&self, issue: &Issue, body: &str) -> Result<Comment> {
+        api_requests::create_comment(&self.profile, issue.repository_name(), issue.number, body)
+            .await
+    }
+
+    async fn fetch_issue_details(&self, issues: &mut [Issue]) {
+        api_requests::fetch_pull_request_details(&self.profile, issues).await
+    }
+
+    async fn fetch_pull_request(&self, repo: &str, number: usize) -> Result<PullRequest> {
+        api_requests::fetch_pull_request(&self.profile, repo, number).await
+    }
+
+    async fn submit_review(
+        &self,
+        repo: &str,
+        number: usize,
+        event: ReviewEvent,
+        body: String,
+    ) -> Result<Review> {
+        api_requests::submit_review(&self.profile, repo, number, event, body).await
+    }
+
+    async fn merge_pull_request(
+        &self,
+        repo: &str,
+        number: usize,
+        merge: &MergeRequest,
+    ) -> Result<MergeResult> {
+        api_requests::merge_pull_request(&self.profile, repo, number, merge).await
+    }
+
+    async fn delete_branch(&self, repo: &str, branch: &str) -> Result<()> {
+        api_requests::delete_branch(&self.profile, repo, branch).await
+    }
+
+    async fn fetch_discussions(&self, repo: &str) -> Result<Vec<Discussion>> {
+        api_requests::fetch_discussions(&self.profile, repo).await
+    }
+
+    async fn reply_discussion(
+        &self,
+        discussion_id: &str,
+        reply_to: Option<&str>,
+        body: String,
+    ) -> Result<DiscussionComment> {
+        api_requests::reply_discussion(&self.profile, discussion_id, reply_to, body).await
+    }
+
+    async fn fetch_releases(&self, repo: &str) -> Result<Vec<Release>> {
+        api_requests::fetch_releases(&self.profile, repo).await
+    }
+
+    async fn fetch_workflow_runs(&self, repo: &str) -> Result<Vec<WorkflowRun>> {
+        api_requests::fetch_workflow_runs(&self.profile, repo).await
+    }
+
+    async fn fetch_jobs(&self, repo: &str, run_id: u64) -> Result<Vec<Job>> {
+        api_requests::fetch_jobs(&self.profile, repo, run_id).await
+    }
+
+    async fn fetch_job_logs(&self, repo: &str, job_id: u64) -> Result<String> {
+        api_requests::fetch_job_logs(&self.profile, repo, job_id).await
+    }
+
+    async fn rerun_failed_jobs(&self, repo: &str, run_id: u64) -> Result<()> {
+        api_requests::rerun_failed_jobs(&self.profile, repo, run_id).await
+    }
+
+    async fn cancel_workflow_run(&self, repo: &str, run_id: u64) -> Result<()> {
+        api_requests::cancel_workflow_run(&self.profile, repo, run_id).await
+    }
+
+    async fn fetch_projects(&self, repo: &str) -> Result<Vec<Project>> {
+        api_requests::fetch_projects(&self.profile, repo).await
+    }
+
+    async fn update_project_field(
+        &self,
+        project_id: &str,
+        item_id: &str,
+        field: &ProjectField,
+        value: Option<&str>,
+    ) -> Result<()> {
+        api_requests::update_project_field(&self.profile, project_id, item_id, field, value).await
+    }
+}
@@ -0,0 +1,562 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use futures::future::join_all;
+use reqwest::{Method, RequestBuilder, Response, StatusCode};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::Forge;
+use crate::{
+    api_requests::RequestError,
+    models::{
+        comment::Comment,
+        issue::{Issue, IssuePullRequest, Label, Milestone, NewIssue},
+        issue_template::IssueTemplate,
+        repository::{License, Repository},
+        user::{AuthenticatedUser, User, UserProfile},
+    },
+};
+
+/// The REST API (v4) of gitlab.com or a self-managed GitLab instance.
+///
+/// Projects are addressed by their full path, e.g. `group/subgroup/project`.
+pub struct Gitlab {
+    /// Url of the instance, without a trailing slash
+    url: String,
+    token: String,
+    client: reqwest::Client,
+}
+
+#[derive(Deserialize)]
+struct Project {
+    path: String,
+    path_with_namespace: String,
+    /// Only present when issues are enabled for the project
+    #[serde(default)]
+    open_issues_count: isize,
+    default_branch: Option<String>,
+    description: Option<String>,
+    #[serde(default)]
+    topics: Vec<String>,
+    #[serde(default)]
+    star_count: usize,
+    #[serde(default)]
+    forks_count: usize,
+    license: Option<License>,
+    last_activity_at: Option<String>,
+}
+
+impl From<Project> for Repository {
+    fn from(project: Project) -> Self {
+        Self {
+            name: project.path,
+            full_name: project.path_with_namespace,
+            open_issues_count: project.open_issues_count,
+            default_branch: project.default_branch.unwrap_or_default(),
+            description: project.description,
+            topics: project.topics,
+            stargazers_count: project.star_count,
+            forks_count: project.forks_count,
+            license: project.license,
+            pushed_at: project.last_activity_at,
+            placeholder: false,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Author {
+    username: String,
+}
+
+/// An issue or merge request.
+#[derive(Deserialize)]
+struct GitlabIssue {
+    iid: usize,
+    title: String,
+    description: Option<String>,
+    web_url: String,
+    author: Author,
+    #[serde(default)]
+    user_notes_count: usize,
+    references: References,
+    #[serde(default)]
+    labels: Vec<String>,
+    milestone: Option<Milestone>,
+    #[serde(default)]
+    updated_at: String,
+}
+
+#[derive(Deserialize)]
+struct References {
+    /// e.g. `group/project#12` for issues and `group/project!3` for merge requests
+    full: String,
+}
+
+#[derive(Deserialize)]
+struct Note {
+    body: String,
+    author: Author,
+    created_at: String,
+    #[serde(default)]
+    system: bool,
+}
+
+impl From<Note> for Comment {
+    fn from(note: Note) -> Self {
+        Self {
+            body: note.body,
+            user: User {
+                login: note.author.username,
+            },
+            created_at: note.created_at,
+            author_association: Default::default(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct GitlabUser {
+    id: u64,
+    username: String,
+    name: Option<String>,
+    bio: Option<String>,
+    organization: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TreeEntry {
+    name: String,
+    path: String,
+    #[serde(rename = "type")]
+    kind: String,
+}
+
+#[derive(Serialize)]
+struct NewGitlabIssue {
+    title: String,
+    description: String,
+    /// Comma separated label names
+    labels: String,
+    assignee_ids: Vec<u64>,
+}
+
+/// Encode a project or file path for use as a single url path segment.
+fn encode_path(path: &str) -> String {
+    path.replace('/', "%2F")
+}
+
+/// Turn an unsuccessful response into an error carrying GitLab's message.
+async fn check_response(raw: Response) -> Result<Response> {
+    let status = raw.status();
+
+    if !status.is_success() {
+        // GitLab responds with `message` or `error`, either of which may not be a string
+        let message = match raw.json::<Value>().await {
+            Ok(body) => match body.get("message").or_else(|| body.get("error")) {
+                Some(Value::String(message)) => message.clone(),
+                Some(message) => message.to_string(),
+                None => body.to_string(),
+            },
+            Err(_) => String::from(status.canonical_reason().unwrap_or("Unknown error")),
+        };
+
+        return Err(RequestError { status, message }.into());
+    }
+
+    Ok(raw)
+}
+
+impl Gitlab {
+    pub fn new(url: &str, token: &str) -> Self {
+        Self {
+            url: url.trim_end_matches('/').to_string(),
+            token: token.to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.client
+            .request(method, format!("{}/api/v4/{}", self.url, path))
+            .header("PRIVATE-TOKEN", &self.token)
+    }
+
+    async fn fetch<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let raw = self.request(Method::GET, path).send().await?;
+
+        Ok(check_response(raw).await?.json::<T>().await?)
+    }
+
+    async fn send<B: Serialize, T: DeserializeOwned>(&self, path: &str, body: &B) -> Result<T> {
+        let raw = self.request(Method::POST, path).json(body).send().await?;
+
+        Ok(check_response(raw).await?.json::<T>().await?)
+    }
+
+    /// Fetch the raw content of `file` on the default branch of `repo`, `None` when it does not exist.
+    async fn fetch_file(&self, repo: &str, file: &str) -> Result<Option<String>> {
+        let raw = self
+            .request(
+                Method::GET,
+                format!(
+                    "projects/{}/repository/files/{}/raw?ref=HEAD",
+                    encode_path(repo),
+                    encode_path(file)
+                )
+                .as_str(),
+            )
+            .send()
+            .await?;
+
+        if raw.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        Ok(Some(check_response(raw).await?.text().await?))
+    }
+
+    /// The api path of an issue or merge request.
+    fn issue_path(issue: &Issue) -> String {
+        format!(
+            "projects/{}/{}/{}",
+            encode_path(issue.repository_name()),
+            if issue.is_pull_request() {
+                "merge_requests"
+            } else {
+                "issues"
+            },
+            issue.number
+        )
+    }
+
+    fn to_issue(&self, issue: GitlabIssue) -> Issue {
+        let (project, _) = issue
+            .references
+            .full
+            .rsplit_once(['#', '!'])
+            .unwrap_or(("", ""));
+        let is_merge_request = issue.references.full.contains('!');
+
+        Issue {
+            pull_request: is_merge_request.then(|| IssuePullRequest {
+                html_url: issue.web_url.clone(),
+            }),
+            html_url: issue.web_url,
+            repository: project.to_string(),
+            number: issue.iid,
+            sigil: if is_merge_request { '!' } else { '#' },
+            title: issue.title,
+            body: issue.description.unwrap_or_default(),
+            user: User {
+                login: issue.author.username,
+            },
+            author_association: Default::default(),
+            comments: issue.user_notes_count,
+            labels: issue
+                .labels
+                .into_iter()
+                .map(|name| Label { name })
+                .collect(),
+            milestone: issue.milestone,
+            updated_at: issue.updated_at,
+            checks: None,
+            reviews: vec![],
+        }
+    }
+
+    async fn fetch_issue_list(&self, path: &str) -> Result<Vec<Issue>> {
+        Ok(self
+            .fetch::<Vec<GitlabIssue>>(path)
+            .await?
+            .into_iter()
+            .map(|issue| self.to_issue(issue))
+            .collect())
+    }
+
+    async fn fetch_user_by_name(&self, login: &str) -> Result<GitlabUser> {
+        self.fetch::<Vec<GitlabUser>>(format!("users?username={}", login).as_str())
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("No GitLab user named {}", login))
+    }
+}
+
+#[async_trait]
+impl Forge for Gitlab {
+    async fn fetch_repositories(&self) -> Result<Vec<Repository>> {
+        Ok(self
+            .fetch::<Vec<Project>>(
+                "projects?membership=true&order_by=last_activity_at&per_page=100",
+            )
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+
+    async fn fetch_repository(&self, repo: &str) -> Result<Repository> {
+        Ok(self
+            .fetch::<Project>(format!("projects/{}?license=true", encode_path(repo)).as_str())
+            .await?
+            .into())
+    }
+
+    async fn fetch_issues_self(&self) -> Result<Vec<Issue>> {
+        self.fetch_issue_list("issues?scope=assigned_to_me&state=opened&per_page=100")
+            .await
+    }
+
+    /// The open issues of `repo` followed by its open merge requests.
+    async fn fetch_issues(&self, repo: &str) -> Result<Vec<Issue>> {
+        let issues_path = format!(
+            "projects/{}/issues?state=opened&per_page=100",
+            encode_path(repo)
+        );
+        let merge_requests_path = format!(
+            "projects/{}/merge_requests?state=opened&per_page=100",
+            encode_path(repo)
+        );
+
+        let (issues, merge_requests) = tokio::join!(
+            self.fetch_issue_list(issues_path.as_str()),
+            self.fetch_issue_list(merge_requests_path.as_str()),
+        );
+
+        let mut issues = issues?;
+        issues.extend(merge_requests?);
+
+        Ok(issues)
+    }
+
+    async fn fetch_issue(&self, issue: &Issue) -> Result<Issue> {
+        Ok(self.to_issue(
+            self.fetch::<GitlabIssue>(Self::issue_path(issue).as_str())
+                .await?,
+        ))
+    }
+
+    async fn fetch_issues_by(&self, repo: &str, login: &str) -> Result<Vec<Issue>> {
+        self.fetch_issue_list(
+            format!(
+                "projects/{}/issues?author_username={}&state=opened",
+                encode_path(repo),
+                login
+            )
+            .as_str(),
+        )
+        .await
+    }
+
+    async fn fetch_comments(&self, issue: &Issue) -> Result<Vec<Comment>> {
+        Ok(self
+            .fetch::<Vec<Note>>(
+                format!("{}/notes?sort=asc&per_page=100", Self::issue_path(issue)).as_str(),
+            )
+            .await?
+            .into_iter()
+            // System notes record events like label changes
+            .filter(|note| !note.system)
+            .map(Into::into)
+            .collect())
+    }
+
+    async fn fetch_user(&self, login: &str) -> Result<UserProfile> {
+        let user = self.fetch_user_by_name(login).await?;
+
+        Ok(UserProfile {
+            login: user.username,
+            name: user.name,
+            company: user.organization,
+            bio: user.bio,
+        })
+    }
+
+    async fn fetch_authenticated_user(&self) -> Result<AuthenticatedUser> {
+        let user = self.fetch::<GitlabUser>("user").await?;
+
+        Ok(AuthenticatedUser {
+            login: user.username,
+            scopes: None,
+        })
+    }
+
+    async fn fetch_readme(&self, repo: &str) -> Result<Option<String>> {
+        self.fetch_file(repo, "README.md").await
+    }
+
+    /// Parse the markdown templates in `.gitlab/issue_templates`.
+    async fn fetch_issue_templates(&self, repo: &str) -> Result<Vec<IssueTemplate>> {
+        let raw = self
+            .request(
+                Method::GET,
+                format!(
+                    "projects/{}/repository/tree?path=.gitlab/issue_templates&per_page=100",
+                    encode_path(repo)
+                )
+                .as_str(),
+            )
+            .send()
+            .await?;
+
+        if raw.status() == StatusCode::NOT_FOUND {
+            return Ok(vec![]);
+        }
+
+        let entries = check_response(raw)
+            .await?
+            .json::<Vec<TreeEntry>>()
+            .await?
+            .into_iter()
+            .filter(|entry| entry.kind == "blob" && entry.name.ends_with(".md"))
+            .collect::<Vec<TreeEntry>>();
+
+        let contents = join_all(
+            entries
+                .iter()
+                .map(|entry| self.fetch_file(repo, entry.path.as_str())),
+        )
+        .await;
+
+        let mut templates = vec![];
+
+        for (entry, content) in entries.iter().zip(contents) {
+            let name = entry.name.trim_end_matches(".md");
+
+            // A malformed template doesn't keep the others from being offered
+            if let Some(Ok(template)) =
+                content?.map(|content| IssueTemplate::parse(name, content.as_str()))
+            {
+                templates.push(template);
+            }
+        }
+
+        templates.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(templates)
+    }
+
+    async fn create_issue(&self, repo: &str, issue: &NewIssue) -> Result<Issue> {
+        // GitLab assigns users by id
+        let assignee_ids = join_all(
+            issue
+                .assignees
+                .iter()
+                .map(|login| self.fetch_user_by_name(login.as_str())),
+        )
+        .await
+        .into_iter()
+        .map(|user| user.map(|user| user.id))
+        .collect::<Result<Vec<u64>>>()?;
+
+        let created = self
+            .send::<NewGitlabIssue, GitlabIssue>(
+                format!("projects/{}/issues", encode_path(repo)).as_str(),
+                &NewGitlabIssue {
+                    title: issue.title.clone(),
+                    description: issue.body.clone(),
+                    labels: issue.labels.join(","),
+                    assignee_ids,
+                },
+            )
+            .await?;
+
+        Ok(self.to_issue(created))
+    }
+
+    async fn create_comment(&self, issue: &Issue, body: &str) -> Result<Comment> {
+        Ok(self
+            .send::<Value, Note>(
+                format!("{}/notes", Self::issue_path(issue)).as_str(),
+                &json!({ "body": body }),
+            )
+            .await?
+            .into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_server::serve;
+
+    const NOT_FOUND: &str = "404 Project Not Found";
+
+    fn gitlab_issue(kind: &str, reference: &str) -> Value {
+        json!({
+            "iid": 12,
+            "title": format!("Title of {}", reference),
+            "description": null,
+            "web_url": format!("https://gitlab.example.com/group/sub/project/-/{}/12", kind),
+            "author": { "username": "alice" },
+            "user_notes_count": 1,
+            "references": { "full": format!("group/sub/project{}", reference) },
+            "labels": ["bug"],
+            "milestone": null,
+        })
+    }
+
+    #[tokio::test]
+    async fn tells_issues_and_merge_requests_of_the_same_number_apart() {
+        let routes = vec![
+            (
+                "/api/v4/projects/group%2Fsub%2Fproject/issues?state=opened&per_page=100",
+                json!([gitlab_issue("issues", "#12")]),
+            ),
+            (
+                "/api/v4/projects/group%2Fsub%2Fproject/merge_requests?state=opened&per_page=100",
+                json!([gitlab_issue("merge_requests", "!12")]),
+            ),
+            (
+                "/api/v4/projects/group%2Fsub%2Fproject/merge_requests/12/notes?sort=asc&per_page=100",
+                json!([
+                    {
+                        "body": "added 1 commit",
+                        "author": { "username": "alice" },
+                        "created_at": "2023-01-01T00:00:00Z",
+                        "system": true,
+                    },
+                    {
+                        "body": "Looks good",
+                        "author": { "username": "bob" },
+                        "created_at": "2023-01-02T00:00:00Z",
+                    },
+                ]),
+            ),
+        ];
+
+        let url = serve(routes, NOT_FOUND).await;
+        let gitlab = Gitlab::new(url.as_str(), "token");
+        let issues = gitlab.fetch_issues("group/sub/project").await.unwrap();
+
+        assert_eq!(issues.len(), 2);
+        assert!(issues
+            .iter()
+            .all(|issue| issue.repository_name() == "group/sub/project"));
+        assert!(!issues[0].is_pull_request());
+        assert!(issues[1].is_pull_request());
+        assert_eq!(issues[0].reference(), "#12");
+        assert_eq!(issues[1].reference(), "!12");
+        assert_eq!(issues[1].to_string(), "!12: Title of !12");
+
+        // The notes of the merge request, not of the issue
+        let comments = gitlab.fetch_comments(&issues[1]).await.unwrap();
+
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].body, "Looks good");
+        assert_eq!(comments[0].user.login, "bob");
+    }
+
+    #[tokio::test]
+    async fn surfaces_gitlab_error_messages() {
+        let url = serve(vec![], NOT_FOUND).await;
+        let gitlab = Gitlab::new(url.as_str(), "token");
+
+        let err = gitlab.fetch_issues("group/missing").await.err().unwrap();
+        let err = err.downcast_ref::<RequestError>().unwrap();
+
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+        assert_eq!(err.message, NOT_FOUND);
+    }
+}
@@ -0,0 +1,481 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+use super::Forge;
+use crate::{
+    models::{
+        comment::Comment,
+        issue::{Issue, Label, Milestone, NewIssue},
+        issue_template::{split_front_matter, IssueTemplate},
+        repository::Repository,
+        user::{AuthenticatedUser, User, UserProfile},
+    },
+    timestamp,
+};
+
+/// Issues kept as markdown files in a local directory.
+///
+/// Each repository is a `{owner}/{repo}` directory below the root holding a `{number}.md` file
+/// per issue, with the issue details and comments in YAML front-matter. Issue templates are
+/// read from a `templates` directory next to the issues.
+pub struct LocalStore {
+    root: PathBuf,
+    /// The author of new issues and comments
+    user_name: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+enum IssueState {
+    #[default]
+    Open,
+    Closed,
+}
+
+/// The front-matter of an issue file.
+#[derive(Serialize, Deserialize)]
+struct IssueFile {
+    title: String,
+    #[serde(default)]
+    author: String,
+    #[serde(default)]
+    state: IssueState,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    labels: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    assignees: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    milestone: Option<String>,
+    #[serde(default)]
+    created_at: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    comments: Vec<LocalComment>,
+    /// The markdown after the front-matter
+    #[serde(skip)]
+    body: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct LocalComment {
+    author: String,
+    created_at: String,
+    body: String,
+}
+
+impl From<LocalComment> for Comment {
+    fn from(comment: LocalComment) -> Self {
+        Self {
+            body: comment.body,
+            user: User {
+                login: comment.author,
+            },
+            created_at: comment.created_at,
+            author_association: Default::default(),
+        }
+    }
+}
+
+impl IssueFile {
+    fn parse(content: &str) -> Result<Self> {
+        let (front_matter, body) =
+            split_front_matter(content).ok_or_else(|| anyhow!("Missing front-matter"))?;
+
+        Ok(Self {
+            body: body.trim().to_string(),
+            ..serde_yaml::from_str::<IssueFile>(front_matter)?
+        })
+    }
+
+    fn render(&self) -> Result<String> {
+        Ok(format!(
+            "---\n{}---\n\n{}\n",
+            serde_yaml::to_string(self)?,
+            self.body
+        ))
+    }
+}
+
+/// The names of the directories in `path`, empty when it does not exist.
+async fn directories(path: &Path) -> Result<Vec<String>> {
+    let mut names = vec![];
+
+    let Ok(mut entries) = fs::read_dir(path).await else {
+        return Ok(names);
+    };
+
+    while let Some(entry) = entries.next_entry().await? {
+        if entry.file_type().await?.is_dir() {
+            names.push(entry.file_name().to_string_lossy().to_string());
+        }
+    }
+
+    names.sort();
+
+    Ok(names)
+}
+
+impl LocalStore {
+    pub fn new(root: PathBuf, user_name: &str) -> Self {
+        Self {
+            root,
+            user_name: user_name.to_string(),
+        }
+    }
+
+    fn issue_path(&self, repo: &str, number: usize) -> PathBuf {
+        self.root.join(repo).join(format!("{}.md", number))
+    }
+
+    fn to_issue(&self, repo: &str, number: usize, file: IssueFile) -> Issue {
+        Issue {
+            html_url: format!("file://{}", self.issue_path(repo, number).display()),
+            repository: repo.to_string(),
+            number,
+            sigil: '#',
+            title: file.title,
+            body: file.body,
+            user: User { login: file.author },
+            author_association: Default::default(),
+            comments: file.comments.len(),
+            labels: file.labels.into_iter().map(|name| Label { name }).collect(),
+            milestone: file.milestone.map(|title| Milestone { title }),
+            updated_at: String::new(),
+            pull_request: None,
+            checks: None,
+            reviews: vec![],
+        }
+    }
+
+    async fn read_issue(&self, repo: &str, number: usize) -> Result<IssueFile> {
+        let path = self.issue_path(repo, number);
+        let content = fs::read_to_string(&path).await?;
+
+        IssueFile::parse(content.as_str())
+            .map_err(|err| anyhow!("Invalid issue file {}: {}", path.display(), err))
+    }
+
+    async fn write_issue(&self, repo: &str, number: usize, file: &IssueFile) -> Result<()> {
+        fs::create_dir_all(self.root.join(repo)).await?;
+        fs::write(self.issue_path(repo, number), file.render()?).await?;
+
+        Ok(())
+    }
+
+    /// The numbers of all issues in `repo`, newest first.
+    async fn issue_numbers(&self, repo: &str) -> Result<Vec<usize>> {
+        let mut numbers = vec![];
+
+        let Ok(mut entries) = fs::read_dir(self.root.join(repo)).await else {
+            return Ok(numbers);
+        };
+
+        while let Some(entry) = entries.next_entry().await? {
+            let name = entry.file_name().to_string_lossy().to_string();
+
+            if let Some(number) = name
+                .strip_suffix(".md")
+                .and_then(|number| number.parse::<usize>().ok())
+            {
+                numbers.push(number);
+            }
+        }
+
+        numbers.sort_unstable_by(|a, b| b.cmp(a));
+
+        Ok(numbers)
+    }
+
+    /// The open issues of `repo` matching `filter`.
+    async fn open_issues(
+        &self,
+        repo: &str,
+        filter: impl Fn(&IssueFile) -> bool,
+    ) -> Result<Vec<Issue>> {
+        let mut issues = vec![];
+
+        for number in self.issue_numbers(repo).await? {
+            // A malformed or half-written issue file doesn't keep the others from being listed
+            let Ok(file) = self.read_issue(repo, number).await else {
+                continue;
+            };
+
+            if file.state == IssueState::Open && filter(&file) {
+                issues.push(self.to_issue(repo, number, file));
+            }
+        }
+
+        Ok(issues)
+    }
+}
+
+#[async_trait]
+impl Forge for LocalStore {
+    async fn fetch_repositories(&self) -> Result<Vec<Repository>> {
+        let mut repositories = vec![];
+
+        for owner in directories(&self.root).await? {
+            for name in directories(&self.root.join(owner.as_str())).await? {
+                let full_name = format!("{}/{}", owner, name);
+                repositories.push(self.fetch_repository(full_name.as_str()).await?);
+            }
+        }
+
+        Ok(repositories)
+    }
+
+    async fn fetch_repository(&self, repo: &str) -> Result<Repository> {
+        let open_issues = self.open_issues(repo, |_| true).await?;
+
+        Ok(Repository {
+            open_issues_count: open_issues.len() as isize,
+            // Local repositories have no further details
+            placeholder: false,
+            ..Repository::from_full_name(repo)
+        })
+    }
+
+    async fn fetch_issues_self(&self) -> Result<Vec<Issue>> {
+        let mut issues = vec![];
+
+        for repository in self.fetch_repositories().await? {
+            issues.extend(
+                self.open_issues(repository.full_name.as_str(), |file| {
+                    file.assignees.contains(&self.user_name)
+                })
+                .await?,
+            );
+        }
+
+        Ok(issues)
+    }
+
+    async fn fetch_issues(&self, repo: &str) -> Result<Vec<Issue>> {
+        self.open_issues(repo, |_| true).await
+    }
+
+    async fn fetch_issue(&self, issue: &Issue) -> Result<Issue> {
+        let repo = issue.repository_name();
+        let file = self.read_issue(repo, issue.number).await?;
+
+        Ok(self.to_issue(repo, issue.number, file))
+    }
+
+    async fn fetch_issues_by(&self, repo: &str, login: &str) -> Result<Vec<Issue>> {
+        self.open_issues(repo, |file| file.author == login).await
+    }
+
+    async fn fetch_comments(&self, issue: &Issue) -> Result<Vec<Comment>> {
+        let file = self
+            .read_issue(issue.repository_name(), issue.number)
+            .await?;
+
+        Ok(file.comments.into_iter().map(Into::into).collect())
+    }
+
+    async fn fetch_user(&self, login: &str) -> Result<UserProfile> {
+        Ok(UserProfile {
+            login: login.to_string(),
+            name: None,
+            company: None,
+            bio: None,
+        })
+    }
+
+    async fn fetch_authenticated_user(&self) -> Result<AuthenticatedUser> {
+        Ok(AuthenticatedUser {
+            login: self.user_name.clone(),
+            scopes: None,
+        })
+    }
+
+    async fn fetch_readme(&self, repo: &str) -> Result<Option<String>> {
+        Ok(fs::read_to_string(self.root.join(repo).join("README.md"))
+            .await
+            .ok())
+    }
+
+    async fn fetch_issue_templates(&self, repo: &str) -> Result<Vec<IssueTemplate>> {
+        let mut templates = vec![];
+
+        let Ok(mut entries) = fs::read_dir(self.root.join(repo).join("templates")).await else {
+            return Ok(templates);
+        };
+
+        while let Some(entry) = entries.next_entry().await? {
+            let name = entry.file_name().to_string_lossy().to_string();
+
+            if [".md", ".yml", ".yaml"]
+                .iter()
+                .any(|extension| name.ends_with(extension))
+            {
+                let content = fs::read_to_string(entry.path()).await?;
+
+                // A malformed template doesn't keep the others from being offered
+                if let Ok(template) = IssueTemplate::parse(name.as_str(), content.as_str()) {
+                    templates.push(template);
+                }
+            }
+        }
+
+        templates.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(templates)
+    }
+
+    async fn create_issue(&self, repo: &str, issue: &NewIssue) -> Result<Issue> {
+        let number = self
+            .issue_numbers(repo)
+            .await?
+            .first()
+            .map_or(1, |latest| latest + 1);
+
+        let file = IssueFile {
+            title: issue.title.clone(),
+            author: self.user_name.clone(),
+            state: IssueState::Open,
+            labels: issue.labels.clone(),
+            assignees: issue.assignees.clone(),
+            milestone: None,
+            created_at: timestamp::now(),
+            comments: vec![],
+            body: issue.body.clone(),
+        };
+
+        self.write_issue(repo, number, &file).await?;
+
+        Ok(self.to_issue(repo, number, file))
+    }
+
+    async fn create_comment(&self, issue: &Issue, body: &str) -> Result<Comment> {
+        let repo = issue.repository_name();
+        let mut file = self.read_issue(repo, issue.number).await?;
+
+        let comment = LocalComment {
+            author: self.user_name.clone(),
+            created_at: timestamp::now(),
+            body: body.to_string(),
+        };
+        file.comments.push(comment.clone());
+
+        self.write_issue(repo, issue.number, &file).await?;
+
+        Ok(comment.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ISSUE: &str = "---
+title: Crash on start
+author: alice
+state: closed
+labels:
+- bug
+assignees:
+- bob
+milestone: v1.0
+created_at: 2024-01-01T00:00:00Z
+comments:
+- author: bob
+  created_at: 2024-01-02T00:00:00Z
+  body: |-
+    Can't reproduce
+
+    ```
+    itg --version
+    ```
+---
+
+Starting `itg` panics.
+
+---
+
+With a rule in the body.
+";
+
+    #[test]
+    fn round_trips_issue_files() {
+        let file = IssueFile::parse(ISSUE).unwrap();
+
+        assert_eq!(file.title, "Crash on start");
+        assert!(file.state == IssueState::Closed);
+        assert_eq!(file.labels, ["bug"]);
+        assert_eq!(file.milestone.as_deref(), Some("v1.0"));
+        assert_eq!(
+            file.body,
+            "Starting `itg` panics.\n\n---\n\nWith a rule in the body."
+        );
+
+        assert_eq!(file.render().unwrap(), ISSUE);
+    }
+
+    #[test]
+    fn defaults_missing_front_matter_fields() {
+        let file = IssueFile::parse("---\ntitle: Minimal\n---\n").unwrap();
+
+        assert!(file.state == IssueState::Open);
+        assert!(file.comments.is_empty());
+        assert_eq!(
+            file.render().unwrap(),
+            "---\ntitle: Minimal\nauthor: ''\nstate: open\ncreated_at: ''\n---\n\n\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn stores_created_issues_and_comments() {
+        let root = tempfile::tempdir().unwrap();
+        let store = LocalStore::new(root.path().to_path_buf(), "alice");
+
+        let issue = store
+            .create_issue(
+                "owner/repo",
+                &NewIssue {
+                    title: String::from("First"),
+                    body: String::from("Body"),
+                    labels: vec![String::from("bug")],
+                    assignees: vec![String::from("alice")],
+                },
+            )
+            .await
+            .unwrap();
+        store.create_comment(&issue, "A comment").await.unwrap();
+
+        let issues = store.fetch_issues("owner/repo").await.unwrap();
+        let comments = store.fetch_comments(&issues[0]).await.unwrap();
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].number, 1);
+        assert_eq!(issues[0].repository_name(), "owner/repo");
+        assert_eq!(issues[0].body, "Body");
+        assert_eq!(issues[0].comments, 1);
+        assert_eq!(comments[0].body, "A comment");
+        assert_eq!(comments[0].user.login, "alice");
+        assert_eq!(store.fetch_issues_self().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn skips_malformed_issue_files() {
+        let root = tempfile::tempdir().unwrap();
+        let store = LocalStore::new(root.path().to_path_buf(), "alice");
+
+        store
+            .write_issue(
+                "owner/repo",
+                1,
+                &IssueFile::parse("---\ntitle: Intact\n---\n").unwrap(),
+            )
+            .await
+            .unwrap();
+        std::fs::write(store.issue_path("owner/repo", 2), "---\ntitle: [Half\n").unwrap();
+
+        let issues = store.fetch_issues("owner/repo").await.unwrap();
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].title, "Intact");
+    }
+}
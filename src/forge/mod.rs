@@ -0,0 +1,359 @@
+pub mod gitea;
+pub mod github;
+pub mod gitlab;
+pub mod local;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use core::fmt;
+use std::{path::PathBuf, sync::Arc};
+
+use crate::models::{
+    comment::Comment,
+    config::{BackendType, Profile},
+    discussion::{Discussion, DiscussionComment},
+    issue::{Issue, NewIssue},
+    issue_template::IssueTemplate,
+    project::{Project, ProjectField},
+    pull_request::{MergeRequest, MergeResult, PullRequest},
+    release::Release,
+    repository::Repository,
+    review::{Review, ReviewEvent},
+    user::{AuthenticatedUser, UserProfile},
+    workflow_run::{Job, WorkflowRun},
+};
+
+/// The error of operations a backend does not offer.
+#[derive(Debug)]
+pub struct Unsupported;
+
+impl fmt::Display for Unsupported {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Not supported by this backend.")
+    }
+}
+
+impl std::error::Error for Unsupported {}
+
+/// A service hosting repositories and their issues.
+///
+/// Repositories are addressed by their full `{owner}/{repo}` name.
+#[async_trait]
+pub trait Forge: Send + Sync {
+    /// The repositories of the configured user.
+    async fn fetch_repositories(&self) -> Result<Vec<Repository>>;
+
+    async fn fetch_repository(&self, repo: &str) -> Result<Repository>;
+
+    /// The open issues assigned to the configured user across repositories.
+    async fn fetch_issues_self(&self) -> Result<Vec<Issue>>;
+
+    async fn fetch_issues(&self, repo: &str) -> Result<Vec<Issue>>;
+
+    /// The latest state of `issue`.
+    async fn fetch_issue(&self, issue: &Issue) -> Result<Issue>;
+
+    /// The open issues `login` created in `repo`.
+    async fn fetch_issues_by(&self, repo: &str, login: &str) -> Result<Vec<Issue>>;
+
+    async fn fetch_comments(&self, issue: &Issue) -> Result<Vec<Comment>>;
+
+    async fn fetch_user(&self, login: &str) -> Result<UserProfile>;
+
+    /// The user the token belongs to, failing when the token is not accepted.
+    async fn fetch_authenticated_user(&self) -> Result<AuthenticatedUser>;
+
+    /// The README of `repo`, `None` when it has none.
+    async fn fetch_readme(&self, repo: &str) -> Result<Option<String>>;
+
+    async fn fetch_issue_templates(&self, repo: &str) -> Result<Vec<IssueTemplate>>;
+
+    async fn create_issue(&self, repo: &str, issue: &NewIssue) -> Result<Issue>;
+
+    async fn create_comment(&self, issue: &Issue, body: &str) -> Result<Comment>;
+
+    /// Fill in extra details of listed issues, such as pull request checks and reviews.
+    async fn fetch_issue_details(&self, _issues: &mut [Issue]) {}
+
+    async fn fetch_pull_request(&self, _repo: &str, _number: usize) -> Result<PullRequest> {
+        Err(Unsupported.into())
+    }
+
+    async fn submit_review(
+        &self,
+        _repo: &str,
+        _number: usize,
+        _event: ReviewEvent,
+        _body: String,
+    ) -> Result<Review> {
+        Err(Unsupported.into())
+    }
+
+    async fn merge_pull_request(
+        &self,
+        _repo: &str,
+        _number: usize,
+        _merge: &MergeRequest,
+    ) -> Result<MergeResult> {
+        Err(Unsupported.into())
+    }
+
+    async fn delete_branch(&self, _repo: &str, _branch: &str) -> Result<()> {
+        Err(Unsupported.into())
+    }
+
+    async fn fetch_discussions(&self, _repo: &str) -> Result<Vec<Discussion>> {
+        Err(Unsupported.into())
+    }
+
+    /// Reply to a discussion, threaded under the comment `reply_to` when given.
+    async fn reply_discussion(
+        &self,
+        _discussion_id: &str,
+        _reply_to: Option<&str>,
+        _body: String,
+    ) -> Result<DiscussionComment> {
+        Err(Unsupported.into())
+    }
+
+    async fn fetch_releases(&self, _repo: &str) -> Result<Vec<Release>> {
+        Err(Unsupported.into())
+    }
+
+    async fn fetch_workflow_runs(&self, _repo: &str) -> Result<Vec<WorkflowRun>> {
+        Err(Unsupported.into())
+    }
+
+    async fn fetch_jobs(&self, _repo: &str, _run_id: u64) -> Result<Vec<Job>> {
+        Err(Unsupported.into())
+    }
+
+    async fn fetch_job_logs(&self, _repo: &str, _job_id: u64) -> Result<String> {
+        Err(Unsupported.into())
+    }
+
+    async fn rerun_failed_jobs(&self, _repo: &str, _run_id: u64) -> Result<()> {
+        Err(Unsupported.into())
+    }
+
+    async fn cancel_workflow_run(&self, _repo: &str, _run_id: u64) -> Result<()> {
+        Err(Unsupported.into())
+    }
+
+    async fn fetch_projects(&self, _repo: &str) -> Result<Vec<Project>> {
+        Err(Unsupported.into())
+    }
+
+    /// Set `field` of a project item, clearing it when `value` is `None`.
+    async fn update_project_field(
+        &self,
+        _project_id: &str,
+        _item_id: &str,
+        _field: &ProjectField,
+        _value: Option<&str>,
+    ) -> Result<()> {
+        Err(Unsupported.into())
+    }
+}
+
+/// Connect to the backend of `profile`.
+pub fn connect(profile: &Profile) -> Arc<dyn Forge> {
+    match profile.backend {
+        BackendType::Github => Arc::new(github::Github::new(profile.clone())),
+        BackendType::Gitlab => Arc::new(gitlab::Gitlab::new(
+            Some(profile.host.as_str())
+                .filter(|host| !host.is_empty())
+                .unwrap_or("https://gitlab.com"),
+            profile.token.as_str(),
+        )),
+        BackendType::Gitea => Arc::new(gitea::Gitea::new(
+            Some(profile.host.as_str())
+                .filter(|host| !host.is_empty())
+                .unwrap_or("https://codeberg.org"),
+            profile.token.as_str(),
+        )),
+        BackendType::Local => Arc::new(local::LocalStore::new(
+            match profile.host.as_str() {
+                // Next to the config file by default
+                "" => confy::get_configuration_file_path("issue-tracker", None)
+                    .ok()
+                    .and_then(|path| path.parent().map(|dir| dir.join("issues")))
+                    .unwrap_or(PathBuf::from("issues")),
+                host => PathBuf::from(host),
+            },
+            profile.user_name.as_str(),
+        )),
+    }
+}
+
+/// Connect to the backend of `profile` after checking its token, filling in the user name from
+/// the login the token belongs to when none is set.
+///
+/// Also returns the scopes the token is missing for features of the app.
+pub async fn connect_authenticated(profile: &mut Profile) -> Result<(Arc<dyn Forge>, Vec<String>)> {
+    let forge = connect(profile);
+    let user = forge.fetch_authenticated_user().await?;
+    let missing_scopes = user.missing_scopes();
+
+    if profile.user_name.is_empty() && !user.login.is_empty() {
+        profile.user_name = user.login;
+
+        // Reconnect as the backends keep the user name
+        return Ok((connect(profile), missing_scopes));
+    }
+
+    Ok((forge, missing_scopes))
+}
+
+/// The repositories with open issues, preceded by the default repositories of `profile`.
+pub async fn fetch_profile_repositories(
+    forge: &dyn Forge,
+    profile: &Profile,
+) -> Result<Vec<Repository>> {
+    let mut repositories = forge
+        .fetch_repositories()
+        .await?
+        .into_iter()
+        .filter(|repo| {
+            repo.open_issues_count > 0
+                && !profile
+                    .default_repos
+                    .iter()
+                    .any(|name| name.eq_ignore_ascii_case(repo.full_name.as_str()))
+        })
+        .collect::<Vec<Repository>>();
+
+    for name in profile.default_repos.iter().rev() {
+        let repo = forge
+            .fetch_repository(name.as_str())
+            .await
+            .unwrap_or_else(|_| Repository::from_full_name(name.as_str()));
+
+        repositories.insert(0, repo);
+    }
+
+    Ok(repositories)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A backend serving fixed repositories and offering none of the optional operations.
+    struct Fake {
+        repositories: Vec<Repository>,
+    }
+
+    #[async_trait]
+    impl Forge for Fake {
+        async fn fetch_repositories(&self) -> Result<Vec<Repository>> {
+            Ok(self.repositories.clone())
+        }
+
+        async fn fetch_repository(&self, repo: &str) -> Result<Repository> {
+            self.repositories
+                .iter()
+                .find(|repository| repository.full_name.eq_ignore_ascii_case(repo))
+                .cloned()
+                .ok_or_else(|| anyhow::anyhow!("no repository {}", repo))
+        }
+
+        async fn fetch_issues_self(&self) -> Result<Vec<Issue>> {
+            Ok(vec![])
+        }
+
+        async fn fetch_issues(&self, _repo: &str) -> Result<Vec<Issue>> {
+            Ok(vec![])
+        }
+
+        async fn fetch_issue(&self, issue: &Issue) -> Result<Issue> {
+            Ok(issue.clone())
+        }
+
+        async fn fetch_issues_by(&self, _repo: &str, _login: &str) -> Result<Vec<Issue>> {
+            Ok(vec![])
+        }
+
+        async fn fetch_comments(&self, _issue: &Issue) -> Result<Vec<Comment>> {
+            Ok(vec![])
+        }
+
+        async fn fetch_user(&self, _login: &str) -> Result<UserProfile> {
+            Err(Unsupported.into())
+        }
+
+        async fn fetch_authenticated_user(&self) -> Result<AuthenticatedUser> {
+            Err(Unsupported.into())
+        }
+
+        async fn fetch_readme(&self, _repo: &str) -> Result<Option<String>> {
+            Ok(None)
+        }
+
+        async fn fetch_issue_templates(&self, _repo: &str) -> Result<Vec<IssueTemplate>> {
+            Ok(vec![])
+        }
+
+        async fn create_issue(&self, _repo: &str, _issue: &NewIssue) -> Result<Issue> {
+            Err(Unsupported.into())
+        }
+
+        async fn create_comment(&self, _issue: &Issue, _body: &str) -> Result<Comment> {
+            Err(Unsupported.into())
+        }
+    }
+
+    fn repository(full_name: &str, open_issues_count: isize) -> Repository {
+        Repository {
+            open_issues_count,
+            placeholder: false,
+            ..Repository::from_full_name(full_name)
+        }
+    }
+
+    fn fake() -> Fake {
+        Fake {
+            repositories: vec![
+                repository("owner/quiet", 0),
+                repository("owner/busy", 3),
+                repository("owner/pinned", 1),
+            ],
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_operations_a_backend_does_not_offer() {
+        let forge = fake();
+
+        let unsupported =
+            |err: Option<anyhow::Error>| err.is_some_and(|err| err.is::<Unsupported>());
+
+        assert!(unsupported(
+            forge.fetch_discussions("owner/busy").await.err()
+        ));
+        assert!(unsupported(
+            forge.fetch_pull_request("owner/busy", 1).await.err()
+        ));
+        assert!(unsupported(
+            forge.rerun_failed_jobs("owner/busy", 1).await.err()
+        ));
+    }
+
+    #[tokio::test]
+    async fn lists_default_repositories_first() {
+        let profile = Profile {
+            default_repos: vec![String::from("Owner/Pinned"), String::from("owner/missing")],
+            ..Default::default()
+        };
+
+        let repositories = fetch_profile_repositories(&fake(), &profile).await.unwrap();
+        let names = repositories
+            .iter()
+            .map(|repo| repo.full_name.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(names, ["owner/pinned", "owner/missing", "owner/busy"]);
+        // Repositories that can't be fetched are kept by name
+        assert!(repositories[1].placeholder);
+    }
+}
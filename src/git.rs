@@ -0,0 +1,234 @@
+use anyhow::{bail, Result};
+use std::process::Command;
+
+use crate::models::commit::Commit;
+
+/// Run git in the current directory and return its trimmed stdout.
+fn git(args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        // Never block the TUI on a credential prompt
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()?;
+
+    if !output.status.success() {
+        bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Parse the `{owner}/{repo}` name out of a remote url.
+///
+/// Handles `https://github.com/owner/repo.git`, `git@github.com:owner/repo.git`
+/// and `ssh://git@github.com/owner/repo` style urls. With `nested`, paths of Gitlab subgroups
+/// like `group/subgroup/repo` are accepted too.
+pub fn parse_remote_url(url: &str, nested: bool) -> Option<String> {
+    let path = match url.split_once("://") {
+        Some((_, rest)) => rest.split_once('/')?.1,
+        None => url.split_once(':')?.1,
+    };
+
+    let path = path.trim_end_matches('/').trim_end_matches(".git");
+    let (owner, repo) = path.split_once('/')?;
+
+    if owner.is_empty() || repo.is_empty() || repo.split('/').any(str::is_empty) {
+        return None;
+    }
+
+    if repo.contains('/') && !nested {
+        return None;
+    }
+
+    Some(format!("{}/{}", owner, repo))
+}
+
+/// The repository the `origin` remote of the current directory points at.
+///
+/// `nested` accepts the subgroup paths of Gitlab.
+pub fn origin_repository(nested: bool) -> Option<String> {
+    parse_remote_url(git(&["remote", "get-url", "origin"]).ok()?.as_str(), nested)
+}
+
+/// Find the name of the remote pointing at the repository `repo`.
+pub fn find_remote(repo: &str) -> Option<String> {
+    git(&["remote", "-v"]).ok()?.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        let (name, url) = (parts.next()?, parts.next()?);
+
+        parse_remote_url(url, true)
+            .filter(|remote| remote.eq_ignore_ascii_case(repo))
+            .map(|_| name.to_string())
+    })
+}
+
+/// Whether tracked files in the working tree have uncommitted changes.
+pub fn is_dirty() -> Result<bool> {
+    Ok(!git(&["status", "--porcelain", "--untracked-files=no"])?.is_empty())
+}
+
+/// Fetch the head of pull request `number` from `remote` into a local branch and check it out.
+///
+/// An existing branch is only fast-forwarded, local commits on it are never discarded.
+/// Returns the name of the checked out branch.
+pub fn checkout_pull_request(remote: &str, number: usize) -> Result<String> {
+    let branch = format!("pr-{}", number);
+
+    // Fetching into the branch itself fails while it is checked out
+    git(&[
+        "fetch",
+        remote,
+        format!("refs/pull/{}/head", number).as_str(),
+    ])?;
+
+    let local_ref = format!("refs/heads/{}", branch);
+    if git(&["rev-parse", "--verify", "--quiet", local_ref.as_str()]).is_err() {
+        git(&["checkout", "-b", branch.as_str(), "FETCH_HEAD"])?;
+
+        return Ok(branch);
+    }
+
+    if git(&["merge-base", "--is-ancestor", branch.as_str(), "FETCH_HEAD"]).is_err() {
+        bail!(
+            "Branch {} has commits that are not in pull request #{}, update it by hand",
+            branch,
+            number
+        );
+    }
+
+    git(&["checkout", branch.as_str()])?;
+    git(&["merge", "--ff-only", "FETCH_HEAD"])?;
+
+    Ok(branch)
+}
+
+/// Lowercase `title` and join its words with dashes for use in a branch name.
+fn slugify(title: &str) -> String {
+    title
+        .to_lowercase()
+        // Words in any script are kept, git allows them in branch names
+        .split(|char: char| !char.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .take(8)
+        .collect::<Vec<&str>>()
+        .join("-")
+}
+
+/// Fill in the `{number}` and `{title-slug}` placeholders of a branch name template.
+pub fn branch_name(template: &str, number: usize, title: &str) -> String {
+    let slug = slugify(title);
+    let template = if slug.is_empty() {
+        // Drop the dash joining the slug to the rest of the name
+        template
+            .replace("-{title-slug}", "")
+            .replace("{title-slug}-", "")
+    } else {
+        template.to_string()
+    };
+
+    template
+        .replace("{number}", number.to_string().as_str())
+        .replace("{title-slug}", slug.as_str())
+}
+
+/// Create and check out `branch` from the latest `base` branch of `remote`.
+pub fn create_branch(remote: &str, base: &str, branch: &str) -> Result<()> {
+    git(&["fetch", remote, base])?;
+    git(&[
+        "checkout",
+        "-b",
+        branch,
+        "--no-track",
+        format!("{}/{}", remote, base).as_str(),
+    ])?;
+
+    Ok(())
+}
+
+/// Find commits on any local or remote branch whose message contains `reference`, e.g. `#12`
+/// or `fixes !12`.
+pub fn referencing_commits(reference: &str) -> Result<Vec<Commit>> {
+    let log = git(&[
+        "log",
+        "--all",
+        "--extended-regexp",
+        "--regexp-ignore-case",
+        format!("--grep={}([^0-9]|$)", reference).as_str(),
+        "--format=%h%x1f%an%x1f%s",
+    ])?;
+
+    Ok(log
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\x1f');
+
+            Some(Commit {
+                short_sha: fields.next()?.to_string(),
+                author: fields.next()?.to_string(),
+                subject: fields.next()?.to_string(),
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_remote_urls() {
+        for url in [
+            "https://github.com/owner/repo.git",
+            "git@github.com:owner/repo.git",
+            "ssh://git@github.com/owner/repo/",
+        ] {
+            assert_eq!(parse_remote_url(url, false).as_deref(), Some("owner/repo"));
+        }
+
+        assert_eq!(parse_remote_url("https://github.com/owner", false), None);
+        assert_eq!(parse_remote_url("/srv/git/repo", false), None);
+    }
+
+    #[test]
+    fn parses_nested_remote_urls_only_when_allowed() {
+        let url = "git@gitlab.com:group/subgroup/repo.git";
+
+        assert_eq!(parse_remote_url(url, false), None);
+        assert_eq!(
+            parse_remote_url(url, true).as_deref(),
+            Some("group/subgroup/repo")
+        );
+        assert_eq!(parse_remote_url("git@gitlab.com:group//repo", true), None);
+    }
+
+    #[test]
+    fn slugifies_titles() {
+        assert_eq!(
+            slugify("Fix the  crash on start-up!"),
+            "fix-the-crash-on-start-up"
+        );
+        assert_eq!(slugify("Ünïcode 修正"), "ünïcode-修正");
+        assert_eq!(
+            slugify("one two three four five six seven eight nine"),
+            "one-two-three-four-five-six-seven-eight"
+        );
+    }
+
+    #[test]
+    fn fills_in_branch_templates() {
+        assert_eq!(
+            branch_name("{number}-{title-slug}", 12, "Add a flag"),
+            "12-add-a-flag"
+        );
+        assert_eq!(
+            branch_name("feature/{title-slug}-{number}", 3, "Ünïcode"),
+            "feature/ünïcode-3"
+        );
+    }
+
+    #[test]
+    fn drops_the_dash_of_an_empty_slug() {
+        assert_eq!(branch_name("{number}-{title-slug}", 12, "!!!"), "12");
+        assert_eq!(branch_name("{title-slug}-{number}", 12, ""), "12");
+    }
+}
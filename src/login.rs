@@ -0,0 +1,253 @@
+use anyhow::{bail, Result};
+use crossterm::style::Stylize;
+use reqwest::header::ACCEPT;
+use serde::{de::DeserializeOwned, Deserialize};
+use std::time::Duration;
+
+use crate::{
+    api_requests::fetch_authenticated_user,
+    create_spinner,
+    models::config::{BackendType, Config, LoginConfig, Profile},
+};
+
+/// The codes handed out at the start of the device flow.
+#[derive(Deserialize)]
+struct DeviceCode {
+    device_code: String,
+    user_code: String,
+    verification_uri: String,
+    expires_in: u64,
+    interval: u64,
+}
+
+/// A poll of the token endpoint, an error until the user entered the code.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TokenResponse {
+    Token {
+        access_token: String,
+    },
+    Error {
+        error: String,
+        error_description: Option<String>,
+    },
+}
+
+/// Post `form` to the OAuth endpoint `url`, which answers in JSON when asked to.
+async fn post_form<T: DeserializeOwned>(url: &str, form: &[(&str, &str)]) -> Result<T> {
+    let response = reqwest::Client::new()
+        .post(url)
+        .header(ACCEPT, "application/json")
+        .form(form)
+        .send()
+        .await?
+        .error_for_status()?;
+
+    Ok(response.json::<T>().await?)
+}
+
+/// The device code and token endpoints of `login`, on the web host of `profile` unless set.
+fn endpoints(login: &LoginConfig, profile: &Profile) -> (String, String) {
+    let host = match profile.host.trim_end_matches('/') {
+        "" => "https://github.com",
+        host => host,
+    };
+    let endpoint = |url: &str, path: &str| match url {
+        "" => format!("{}/{}", host, path),
+        url => url.to_string(),
+    };
+
+    (
+        endpoint(login.device_code_url.as_str(), "login/device/code"),
+        endpoint(login.token_url.as_str(), "login/oauth/access_token"),
+    )
+}
+
+/// Authorize the OAuth app of `login` with the device flow and return the token.
+async fn device_flow(login: &LoginConfig, profile: &Profile) -> Result<String> {
+    let client_id = login.client_id()?;
+    let (device_code_url, token_url) = endpoints(login, profile);
+
+    let code = post_form::<DeviceCode>(
+        device_code_url.as_str(),
+        &[("client_id", client_id), ("scope", login.scopes.as_str())],
+    )
+    .await?;
+
+    println!(
+        "First copy your one-time code: {}\nThen open {} to enter it.",
+        code.user_code.as_str().bold(),
+        code.verification_uri
+    );
+    webbrowser::open(code.verification_uri.as_str()).ok();
+
+    let spinner = create_spinner(String::from("Waiting for authorization.."));
+    let token = poll_token(token_url.as_str(), client_id, &code).await;
+    spinner.finish_and_clear();
+
+    token
+}
+
+/// Poll `token_url` at the interval of `code` until the user entered it or it expired.
+async fn poll_token(token_url: &str, client_id: &str, code: &DeviceCode) -> Result<String> {
+    let mut interval = code.interval;
+    let mut waited = 0;
+
+    loop {
+        if waited >= code.expires_in {
+            bail!("The code expired. Please log in again.");
+        }
+
+        tokio::time::sleep(Duration::from_secs(interval)).await;
+        waited += interval;
+
+        let response = post_form::<TokenResponse>(
+            token_url,
+            &[
+                ("client_id", client_id),
+                ("device_code", code.device_code.as_str()),
+                ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+            ],
+        )
+        .await?;
+
+        match response {
+            TokenResponse::Token { access_token } => return Ok(access_token),
+            TokenResponse::Error { error, .. } if error == "authorization_pending" => {}
+            // Asked to poll less often
+            TokenResponse::Error { error, .. } if error == "slow_down" => interval += 5,
+            TokenResponse::Error {
+                error,
+                error_description,
+            } => bail!(error_description.unwrap_or(error)),
+        }
+    }
+}
+
+/// Log in to the profile `profile_name` with the device flow and store the token in it.
+///
+/// The user name is filled in from the token when the profile has none yet.
+pub async fn login(profile_name: Option<String>, host: Option<String>) -> Result<()> {
+    let mut config = Config::load(profile_name)?;
+    let name = config.active_profile.clone();
+    let mut profile = config.profiles.get(&name).cloned().unwrap_or_default();
+
+    if let Some(host) = host {
+        profile.host = host;
+    }
+
+    if profile.backend != BackendType::Github {
+        bail!("Logging in is only supported for Github profiles.");
+    }
+
+    let token = device_flow(&config.login, &profile).await?;
+
+    profile.access_token = token.clone();
+    profile.token = token;
+
+    if profile.user_name.is_empty() {
+        profile.user_name = fetch_authenticated_user(&profile).await?.login;
+    }
+
+    println!(
+        "{} Logged in as {} to the profile {}.",
+        "✓".green(),
+        profile.user_name.as_str().bold(),
+        name
+    );
+
+    config.profiles.insert(name, profile);
+    config.store()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_server::serve;
+    use serde_json::json;
+    use tokio::time::Instant;
+
+    const TOKEN_PATH: &str = "/login/oauth/access_token";
+
+    fn device_code(interval: u64, expires_in: u64) -> DeviceCode {
+        DeviceCode {
+            device_code: String::from("device-code"),
+            user_code: String::from("ABCD-1234"),
+            verification_uri: String::from("https://github.com/login/device"),
+            expires_in,
+            interval,
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn polls_until_the_code_is_entered() {
+        let url = serve(
+            vec![
+                (TOKEN_PATH, json!({ "error": "authorization_pending" })),
+                (TOKEN_PATH, json!({ "error": "slow_down" })),
+                (TOKEN_PATH, json!({ "access_token": "token" })),
+            ],
+            "Not Found",
+        )
+        .await;
+
+        let start = Instant::now();
+        let token = poll_token(
+            format!("{}{}", url, TOKEN_PATH).as_str(),
+            "client",
+            &device_code(5, 900),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(token, "token");
+        // Polled after 5s, 5s and, slowed down, 10s
+        assert!(start.elapsed() >= Duration::from_secs(20));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn stops_at_expired_codes() {
+        let url = serve(
+            vec![(
+                TOKEN_PATH,
+                json!({
+                    "error": "expired_token",
+                    "error_description": "The device code has expired.",
+                }),
+            )],
+            "Not Found",
+        )
+        .await;
+
+        let err = poll_token(
+            format!("{}{}", url, TOKEN_PATH).as_str(),
+            "client",
+            &device_code(5, 900),
+        )
+        .await
+        .err()
+        .unwrap();
+
+        assert_eq!(err.to_string(), "The device code has expired.");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn gives_up_once_the_code_expires() {
+        let url = serve(
+            vec![(TOKEN_PATH, json!({ "error": "authorization_pending" }))],
+            "Not Found",
+        )
+        .await;
+
+        let err = poll_token(
+            format!("{}{}", url, TOKEN_PATH).as_str(),
+            "client",
+            &device_code(5, 10),
+        )
+        .await
+        .err()
+        .unwrap();
+
+        assert_eq!(err.to_string(), "The code expired. Please log in again.");
+    }
+}
@@ -1,22 +1,35 @@
 pub mod api_requests;
 pub mod controls;
+pub mod editor;
+pub mod events;
+pub mod forge;
+pub mod git;
+pub mod login;
 pub mod models;
+pub mod timestamp;
 pub mod ui;
 
-use anyhow::Result;
-use api_requests::fetch_repositories;
+#[cfg(test)]
+mod test_server;
+
+use anyhow::{bail, Result};
 use clap::Parser;
-use controls::run_app;
+use controls::{load_origin_issues, run_app, run_setup};
+use events::Events;
 use indicatif::{ProgressBar, ProgressStyle};
 use models::{
-    app_state::AppState, args::Args, config::Config, issue::Issue, menu_items::MenuItems,
-    repository::Repository,
+    app_state::AppState,
+    args::{Args, Command},
+    config::{BackendType, Config, Profile},
+    errors::Errors,
+    issue::Issue,
+    menu_items::MenuItems,
+    setup_wizard::SetupWizard,
 };
 use std::{io, time::Duration};
 
-use crossterm::{
-    style::Stylize,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
 };
 use tui::{backend::CrosstermBackend, Terminal};
 
@@ -37,44 +50,89 @@ fn create_spinner(message: String) -> ProgressBar {
 async fn main() -> Result<()> {
     let args = Args::parse();
 
-    let config = Config::initialise_config(Config {
-        github_access_token: args.token.unwrap_or(String::new()),
-        user_name: args.user_name.unwrap_or(String::new()),
-    });
+    if let Some(Command::Login { host }) = args.command {
+        return login::login(args.profile, host).await;
+    }
 
     if args.file_path {
         eprintln!(
             "{:?}",
-            confy::get_configuration_file_path("issue-tracker", None).unwrap()
+            confy::get_configuration_file_path("issue-tracker", None)?
         );
-        reset_terminal().unwrap_or_else(|_| panic!("Failed to reset terminal"));
-        std::process::exit(1);
+
+        return Ok(());
+    }
+
+    let mut config = Config::initialise_config(
+        args.profile,
+        Profile {
+            access_token: args.token.unwrap_or(String::new()),
+            user_name: args.user_name.unwrap_or(String::new()),
+            ..Default::default()
+        },
+    )?;
+
+    // Ask for the missing values on the first run
+    if let Err(err) = config.check_empty_values() {
+        let profile = config.profile();
+        let wizard = SetupWizard::new(config.active_profile.clone(), &profile, err.to_string());
+
+        let mut terminal = init_terminal()?;
+        let res = run_setup(&mut terminal, wizard, profile).await;
+        reset_terminal()?;
+
+        match res? {
+            Some(profile) => {
+                config
+                    .profiles
+                    .insert(config.active_profile.clone(), profile);
+                config.store()?;
+            }
+            None => return Ok(()),
+        }
     }
 
     let spinner = create_spinner(String::from("Fetching data.."));
-    let repositories = fetch_repositories(&config).await?;
-    let repositories = repositories
-        .iter()
-        .cloned()
-        .filter(|repo| repo.open_issues_count > 0)
-        .collect::<Vec<Repository>>();
+    let mut profile = config.profile();
+    let (forge, missing_scopes) = match forge::connect_authenticated(&mut profile).await {
+        Ok(connection) => connection,
+        Err(err) => {
+            spinner.finish_and_clear();
+            bail!("{} {}", Errors::InvalidTokenError, err);
+        }
+    };
+    let repositories = forge::fetch_profile_repositories(forge.as_ref(), &profile).await?;
+
+    // Use the filled in user name for this session
+    config
+        .profiles
+        .insert(config.active_profile.clone(), profile);
+
+    let events = Events::new();
+
+    if config.refresh_interval > 0 {
+        events.refresh_every(Duration::from_secs(config.refresh_interval));
+    }
+
+    let mut app_state = AppState::new(config, forge, events.sender(), repositories);
+
+    // Preselect the repository itg was launched inside of
+    // Github repositories are never nested, Gitlab ones may live in subgroups
+    let nested = app_state.profile.backend != BackendType::Github;
+    if let Some(full_name) = git::origin_repository(nested) {
+        load_origin_issues(&mut app_state, full_name);
+    }
+
+    app_state.show_missing_scopes(missing_scopes);
 
     spinner.finish();
 
     let mut terminal = init_terminal()?;
-
-    let app_state = AppState::new(config, repositories);
-    let res = run_app(&mut terminal, app_state).await;
+    let res = run_app(&mut terminal, app_state, events).await;
 
     reset_terminal()?;
 
-    if let Err(err) = res {
-        eprintln!("{}: {}", "Error".red().bold(), err);
-        reset_terminal().unwrap_or_else(|_| panic!("Failed to reset terminal"));
-        std::process::exit(1);
-    }
-
-    Ok(())
+    res
 }
 
 fn init_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>> {
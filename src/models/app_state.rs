@@ -1,23 +1,101 @@
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    sync::Arc,
+};
+use tokio::sync::mpsc::UnboundedSender;
 
-use crate::Issue;
+use crate::{
+    events::{AppEvent, Load, Loaded},
+    forge::Forge,
+    Issue,
+};
 
 use super::{
-    config::Config, input_mode::InputMode, menu_items::MenuItems, popup::Popup,
-    repository::Repository, screen::Screen, stateful_list::StatefulList,
+    checks::Check,
+    comment::Comment,
+    commit::Commit,
+    config::{Config, Profile},
+    discussion::{Discussion, DiscussionComment},
+    field_editor::FieldEditor,
+    input_mode::InputMode,
+    issue_form::IssueForm,
+    issue_template::TemplatePicker,
+    menu_items::MenuItems,
+    merge_dialog::MergeDialog,
+    popup::Popup,
+    profile_picker::ProfilePicker,
+    project::{Board, Project},
+    release::Release,
+    repository::Repository,
+    screen::Screen,
+    stateful_list::StatefulList,
+    workflow_run::{Job, WorkflowRun},
 };
 
 pub struct AppState {
     /// App config file
     pub config: Config,
+    /// The profile in use
+    pub profile: Profile,
+    /// The backend repositories and issues are loaded from
+    pub forge: Arc<dyn Forge>,
+    /// Posts the results of background fetches to the app loop
+    pub events: UnboundedSender<AppEvent>,
+    /// The background fetches in flight
+    pub loading: HashSet<Load>,
+    /// Counts profile switches, results of fetches started with an earlier profile are dropped
+    pub generation: usize,
+    /// The profile being switched to, only the latest pick is switched to
+    pub profile_switch: Option<String>,
+    /// Ticks since the app opened, animating loading spinners
+    pub ticks: usize,
     /// The current menu item
     pub current_menu: MenuItems,
     /// All issues in the current selected repository
     pub issues: StatefulList<Issue>,
+    /// Checks of the selected pull request
+    pub checks: StatefulList<Check>,
+    /// Local commits referencing the selected issue
+    pub commits: Vec<Commit>,
     /// A cache of issues
     pub issue_cache: HashMap<String, Vec<Issue>>,
+    /// The issues assigned to the user, once fetched
+    pub assigned_cache: Option<Vec<Issue>>,
+    /// Whether the issue list shows the issues assigned to the user
+    pub shows_assigned: bool,
+    /// When each fetched issue last changed, keyed by issue url
+    pub issue_updates: HashMap<String, String>,
+    /// Urls of issues that are new or changed since they were last viewed
+    pub unseen_issues: HashSet<String>,
+    /// A cache of local commits referencing an issue, keyed by issue url
+    pub commit_cache: HashMap<String, Vec<Commit>>,
+    /// Comments on the selected issue
+    pub comments: Vec<Comment>,
+    /// A cache of issue comments, keyed by issue url
+    pub comment_cache: HashMap<String, Vec<Comment>>,
+    /// All discussions in the current selected repository
+    pub discussions: StatefulList<Discussion>,
+    /// The comment highlighted in the preview while comments are focused
+    pub selected_comment: usize,
+    /// A cache of discussions, keyed by repository name
+    pub discussion_cache: HashMap<String, Vec<Discussion>>,
+    /// All releases of the current selected repository
+    pub releases: StatefulList<Release>,
+    /// A cache of releases, keyed by repository name
+    pub release_cache: HashMap<String, Vec<Release>>,
+    /// Recent workflow runs of the current selected repository
+    pub runs: StatefulList<WorkflowRun>,
+    /// Jobs of the opened workflow run
+    pub jobs: StatefulList<Job>,
+    /// Projects linked to the current selected repository
+    pub projects: Vec<Project>,
+    /// The project shown on the board
+    pub board: Option<Board>,
     /// All repositories fetched when the app opened
     pub repositories: StatefulList<Repository>,
+    /// A cache of repository READMEs, keyed by repository name
+    pub readme_cache: HashMap<String, Option<String>>,
     /// The selected repository
     pub selected_repo: Option<Repository>,
     /// The current focused screen
@@ -26,38 +104,475 @@ pub struct AppState {
     pub input_mode: InputMode,
     /// UI Popup
     pub popup: Popup,
+    /// The pull request merge being prepared
+    pub merge_dialog: Option<MergeDialog>,
+    /// The project item field being edited
+    pub field_editor: Option<FieldEditor>,
+    /// The templates offered for a new issue
+    pub template_picker: Option<TemplatePicker>,
+    /// The new issue being written
+    pub issue_form: Option<IssueForm>,
+    /// The profiles offered to switch to
+    pub profile_picker: Option<ProfilePicker>,
 }
 
 impl AppState {
-    pub fn new(config: Config, repositories: Vec<Repository>) -> Self {
+    pub fn new(
+        config: Config,
+        forge: Arc<dyn Forge>,
+        events: UnboundedSender<AppEvent>,
+        repositories: Vec<Repository>,
+    ) -> Self {
         Self {
+            profile: config.profile(),
             config,
+            forge,
+            events,
+            loading: HashSet::new(),
+            generation: 0,
+            profile_switch: None,
+            ticks: 0,
             current_menu: MenuItems::Issues,
             issues: StatefulList::with_items(vec![]),
+            checks: StatefulList::with_items(vec![]),
+            commits: vec![],
             issue_cache: HashMap::new(),
+            assigned_cache: None,
+            shows_assigned: false,
+            issue_updates: HashMap::new(),
+            unseen_issues: HashSet::new(),
+            commit_cache: HashMap::new(),
+            comments: vec![],
+            comment_cache: HashMap::new(),
+            discussions: StatefulList::with_items(vec![]),
+            selected_comment: 0,
+            discussion_cache: HashMap::new(),
+            releases: StatefulList::with_items(vec![]),
+            release_cache: HashMap::new(),
+            runs: StatefulList::with_items(vec![]),
+            jobs: StatefulList::with_items(vec![]),
+            projects: vec![],
+            board: None,
             repositories: StatefulList::with_items(repositories),
+            readme_cache: HashMap::new(),
             selected_repo: None,
             screen: Screen::Issues,
             input_mode: InputMode::Normal,
             popup: Popup::default(),
+            merge_dialog: None,
+            field_editor: None,
+            template_picker: None,
+            issue_form: None,
+            profile_picker: None,
         }
     }
 
+    /// Continue with `profile` named `name`, dropping everything loaded with the previous one.
+    pub fn switch_profile(
+        &mut self,
+        name: String,
+        profile: Profile,
+        forge: Arc<dyn Forge>,
+        repositories: Vec<Repository>,
+    ) {
+        let mut config = self.config.clone();
+        // Keep the resolved token for switching back
+        config.profiles.insert(name.clone(), profile);
+        config.active_profile = name;
+
+        // Fetches still in flight for the previous profile are ignored once they finish
+        let generation = self.generation + 1;
+        *self = AppState::new(config, forge, self.events.clone(), repositories);
+        self.generation = generation;
+    }
+
+    /// Run `fetch` in the background unless `load` is already in flight, posting its result to
+    /// the app loop.
+    pub fn spawn_load(&mut self, load: Load, fetch: impl Future<Output = Loaded> + Send + 'static) {
+        if !self.loading.insert(load.clone()) {
+            return;
+        }
+
+        let events = self.events.clone();
+        let generation = self.generation;
+
+        tokio::spawn(async move {
+            // The app closed when the channel is gone
+            events
+                .send(AppEvent::Loaded(generation, load, fetch.await))
+                .ok();
+        });
+    }
+
+    /// Whether a background fetch matching `matches` is in flight.
+    pub fn is_loading(&self, matches: impl Fn(&Load) -> bool) -> bool {
+        self.loading.iter().any(matches)
+    }
+
+    /// The current frame of the loading spinner.
+    pub fn spinner(&self) -> &'static str {
+        const FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+        FRAMES[self.ticks % FRAMES.len()]
+    }
+
+    /// Whether `repository_name` is the selected repository.
+    pub fn is_selected_repo(&self, repository_name: &str) -> bool {
+        self.selected_repo
+            .as_ref()
+            .is_some_and(|repo| repo.full_name == repository_name)
+    }
+
     pub fn change_focus(&mut self) {
         match self.screen {
-            Screen::Issues => self.screen = Screen::Repositories,
-            Screen::Repositories => self.screen = Screen::Issues,
-            Screen::Error => {}
+            Screen::Issues
+            | Screen::Discussions
+            | Screen::Releases
+            | Screen::Runs
+            | Screen::Board => self.screen = Screen::Repositories,
+            Screen::Repositories
+                if self.current_menu == MenuItems::Issues && !self.checks.items.is_empty() =>
+            {
+                self.screen = Screen::Checks
+            }
+            Screen::Repositories
+                if self.current_menu == MenuItems::Actions && !self.jobs.items.is_empty() =>
+            {
+                self.screen = Screen::Jobs
+            }
+            Screen::Repositories | Screen::Checks if self.comment_count() > 0 => {
+                self.selected_comment = 0;
+                self.screen = Screen::Comments
+            }
+            Screen::Repositories | Screen::Checks | Screen::Jobs | Screen::Comments => {
+                self.screen = self.list_screen()
+            }
+            Screen::Error | Screen::Message => {}
+        }
+    }
+
+    /// The number of top level comments in the preview of the current menu.
+    fn comment_count(&self) -> usize {
+        match self.current_menu {
+            MenuItems::Issues => self.comments.len(),
+            MenuItems::Discussions => self
+                .discussions
+                .selected_value()
+                .map_or(0, |discussion| discussion.comments.nodes.len()),
+            _ => 0,
+        }
+    }
+
+    pub fn next_comment(&mut self) {
+        if self.selected_comment + 1 < self.comment_count() {
+            self.selected_comment += 1;
         }
     }
 
+    pub fn previous_comment(&mut self) {
+        self.selected_comment = self.selected_comment.saturating_sub(1);
+    }
+
+    /// The highlighted comment of the selected issue, while comments are focused.
+    pub fn selected_issue_comment(&self) -> Option<&Comment> {
+        if self.screen != Screen::Comments {
+            return None;
+        }
+
+        self.comments.get(self.selected_comment)
+    }
+
+    /// The highlighted comment of the selected discussion, while comments are focused.
+    pub fn selected_discussion_comment(&self) -> Option<&DiscussionComment> {
+        if self.screen != Screen::Comments {
+            return None;
+        }
+
+        self.discussions
+            .selected_value()?
+            .comments
+            .nodes
+            .get(self.selected_comment)
+    }
+
+    /// The screen of the main list of the current menu.
+    pub fn list_screen(&self) -> Screen {
+        match self.current_menu {
+            MenuItems::Issues => Screen::Issues,
+            MenuItems::Discussions => Screen::Discussions,
+            MenuItems::Releases => Screen::Releases,
+            MenuItems::Actions => Screen::Runs,
+            MenuItems::Board => Screen::Board,
+        }
+    }
+
+    pub fn set_menu(&mut self, menu: MenuItems) {
+        self.current_menu = menu;
+
+        if !matches!(self.screen, Screen::Error | Screen::Message) {
+            self.screen = self.list_screen();
+        }
+    }
+
+    /// Replace the listed issues, selecting the first one.
+    pub fn set_issues(&mut self, issues: Vec<Issue>) {
+        self.issues = StatefulList::with_items(issues);
+        self.update_selection();
+    }
+
+    /// Replace the listed issues with refetched ones, keeping the selected issue.
+    pub fn replace_issues(&mut self, issues: Vec<Issue>) {
+        let selected = self
+            .issues
+            .selected_value()
+            .map(|issue| issue.html_url.clone());
+
+        self.issues = StatefulList::with_items(issues);
+
+        if let Some(index) = selected.and_then(|html_url| {
+            self.issues
+                .items
+                .iter()
+                .position(|issue| issue.html_url == html_url)
+        }) {
+            self.issues.state.select(Some(index));
+        }
+
+        self.update_selection();
+    }
+
+    pub fn next_issue(&mut self) {
+        self.issues.next();
+        self.update_selection();
+    }
+
+    pub fn previous_issue(&mut self) {
+        self.issues.previous();
+        self.update_selection();
+    }
+
+    /// Show the checks and the cached local commits and comments of the selected issue.
+    fn update_selection(&mut self) {
+        if let Some(issue) = self.issues.selected_value() {
+            self.unseen_issues.remove(&issue.html_url);
+        }
+
+        let checks = match self.issues.selected_value() {
+            Some(Issue {
+                checks: Some(checks),
+                ..
+            }) => checks.checks.clone(),
+            _ => vec![],
+        };
+
+        self.checks = StatefulList::with_items(checks);
+
+        self.commits = self
+            .issues
+            .selected_value()
+            .and_then(|issue| self.commit_cache.get(&issue.html_url))
+            .cloned()
+            .unwrap_or_default();
+
+        self.comments = self
+            .issues
+            .selected_value()
+            .and_then(|issue| self.comment_cache.get(&issue.html_url))
+            .cloned()
+            .unwrap_or_default();
+    }
+
+    /// Cache the local `commits` referencing the issue with `html_url`.
+    pub fn set_commits(&mut self, html_url: String, commits: Vec<Commit>) {
+        self.commit_cache.insert(html_url, commits);
+        self.update_selection();
+    }
+
+    /// Cache the `comments` of the issue with `html_url`.
+    pub fn set_comments(&mut self, html_url: String, comments: Vec<Comment>) {
+        self.comment_cache.insert(html_url, comments);
+        self.update_selection();
+    }
+
     pub fn cache_issues(&mut self, repository_name: String, issues: Vec<Issue>) {
+        // Everything is new the first time a repository is fetched
+        let refreshed = self.issue_cache.contains_key(&repository_name);
+        self.track_updates(&issues, refreshed);
+
         self.issue_cache.insert(repository_name, issues);
     }
 
+    pub fn cache_assigned_issues(&mut self, issues: Vec<Issue>) {
+        let refreshed = self.assigned_cache.is_some();
+        self.track_updates(&issues, refreshed);
+
+        self.assigned_cache = Some(issues);
+    }
+
+    /// Mark `issues` changed since they were last fetched as unseen, and unknown ones too once
+    /// `refreshed`.
+    fn track_updates(&mut self, issues: &[Issue], refreshed: bool) {
+        for issue in issues {
+            let changed = match self
+                .issue_updates
+                .insert(issue.html_url.clone(), issue.updated_at.clone())
+            {
+                Some(updated_at) => updated_at != issue.updated_at,
+                None => refreshed,
+            };
+
+            if changed {
+                self.unseen_issues.insert(issue.html_url.clone());
+            }
+        }
+    }
+
+    /// Apply `update` to the issue with `html_url` in the issue list and cache.
+    pub fn update_issue(&mut self, html_url: &str, update: impl Fn(&mut Issue)) {
+        self.issues
+            .items
+            .iter_mut()
+            .chain(self.issue_cache.values_mut().flatten())
+            .filter(|issue| issue.html_url == html_url)
+            .for_each(update);
+
+        self.update_selection();
+    }
+
+    /// Replace the listed discussions, caching them for `repository_name`.
+    pub fn set_discussions(&mut self, repository_name: String, discussions: Vec<Discussion>) {
+        self.discussions = StatefulList::with_items(discussions.clone());
+        self.discussion_cache.insert(repository_name, discussions);
+    }
+
+    /// Replace the listed releases, caching them for `repository_name`.
+    pub fn set_releases(&mut self, repository_name: String, releases: Vec<Release>) {
+        self.releases = StatefulList::with_items(releases.clone());
+        self.release_cache.insert(repository_name, releases);
+    }
+
+    /// Replace the listed workflow runs, closing the opened run.
+    pub fn set_runs(&mut self, runs: Vec<WorkflowRun>) {
+        self.runs = StatefulList::with_items(runs);
+        self.jobs = StatefulList::with_items(vec![]);
+    }
+
+    pub fn next_run(&mut self) {
+        self.runs.next();
+        self.jobs = StatefulList::with_items(vec![]);
+    }
+
+    pub fn previous_run(&mut self) {
+        self.runs.previous();
+        self.jobs = StatefulList::with_items(vec![]);
+    }
+
+    /// Replace the listed projects, showing the first one on the board.
+    pub fn set_projects(&mut self, projects: Vec<Project>) {
+        self.board = projects.first().cloned().map(Board::new);
+        self.projects = projects;
+    }
+
+    /// Show the project after the current one on the board.
+    pub fn next_project(&mut self) {
+        let current = self
+            .board
+            .as_ref()
+            .and_then(|board| {
+                self.projects
+                    .iter()
+                    .position(|project| project.id == board.project.id)
+            })
+            .unwrap_or(0);
+
+        if let Some(project) = self
+            .projects
+            .get((current + 1) % self.projects.len().max(1))
+            .cloned()
+        {
+            self.board = Some(Board::new(project));
+        }
+    }
+
+    /// Apply `update` to the discussion with `id` and its cached copy.
+    pub fn update_discussion(&mut self, id: &str, update: impl Fn(&mut Discussion)) {
+        for discussion in self
+            .discussions
+            .items
+            .iter_mut()
+            .chain(self.discussion_cache.values_mut().flatten())
+            .filter(|discussion| discussion.id == id)
+        {
+            update(discussion);
+        }
+    }
+
+    /// Add a newly created issue to the top of the issue list, if it lists its repository, and
+    /// cache.
+    pub fn add_issue(&mut self, issue: Issue) {
+        if let Some(issues) = self.issue_cache.get_mut(issue.repository_name()) {
+            issues.insert(0, issue.clone());
+        }
+
+        if self.shows_assigned || !self.is_selected_repo(issue.repository_name()) {
+            return;
+        }
+
+        self.issues.items.insert(0, issue);
+        self.issues.state.select(Some(0));
+
+        self.update_selection();
+    }
+
+    /// Remove the issue with `html_url` from the issue list and cache.
+    pub fn remove_issue(&mut self, html_url: &str) {
+        for issues in self.issue_cache.values_mut() {
+            issues.retain(|issue| issue.html_url != html_url);
+        }
+
+        let selected = self.issues.selected().unwrap_or(0);
+        self.issues.items.retain(|issue| issue.html_url != html_url);
+        self.issues.state.select(Some(
+            selected.min(self.issues.items.len().saturating_sub(1)),
+        ));
+
+        self.update_selection();
+    }
+
     pub fn select_repo(&mut self, repository: Repository) {
         self.selected_repo = Some(repository)
     }
+
+    /// Move `repository` to the top of the repository list and select it with its issues.
+    pub fn add_repo(&mut self, repository: Repository, issues: Vec<Issue>) {
+        self.set_issues(issues.clone());
+        self.cache_issues(repository.full_name.clone(), issues);
+        self.move_repo_to_top(repository);
+    }
+
+    /// Replace the repository with the same name as `repository` by it.
+    pub fn update_repository(&mut self, repository: Repository) {
+        for repo in self
+            .repositories
+            .items
+            .iter_mut()
+            .chain(self.selected_repo.as_mut())
+            .filter(|repo| repo.full_name == repository.full_name)
+        {
+            *repo = repository.clone();
+        }
+    }
+
+    /// Move `repository` to the top of the repository list and select it.
+    pub fn move_repo_to_top(&mut self, repository: Repository) {
+        self.repositories
+            .items
+            .retain(|repo| repo.full_name != repository.full_name);
+        self.repositories.items.insert(0, repository.clone());
+        self.repositories.state.select(Some(0));
+
+        self.select_repo(repository);
+    }
 }
 
 // Popup related functions
@@ -73,14 +588,255 @@ impl AppState {
         self.input_mode = InputMode::Normal;
     }
 
+    pub fn show_review(&mut self, number: usize) {
+        self.popup.show_popup(
+            format!("Review pull request #{}", number),
+            String::from("a: approve, r: request changes, c: comment, Esc: cancel"),
+        );
+        self.input_mode = InputMode::Review;
+    }
+
+    pub fn hide_review(&mut self) {
+        self.popup.close_popup();
+        self.input_mode = InputMode::Normal;
+    }
+
+    pub fn show_merge(&mut self, merge_dialog: MergeDialog) {
+        self.popup.show_popup(
+            format!("Merge pull request #{}", merge_dialog.pull_request.number),
+            merge_dialog.to_string(),
+        );
+        self.merge_dialog = Some(merge_dialog);
+        self.input_mode = InputMode::Merge;
+    }
+
+    /// Apply `update` to the merge dialog and redraw its popup.
+    pub fn update_merge(&mut self, update: impl FnOnce(&mut MergeDialog)) {
+        if let Some(merge_dialog) = self.merge_dialog.as_mut() {
+            update(merge_dialog);
+            self.popup.message = merge_dialog.to_string();
+        }
+    }
+
+    pub fn hide_merge(&mut self) -> Option<MergeDialog> {
+        self.popup.close_popup();
+        self.input_mode = InputMode::Normal;
+        self.merge_dialog.take()
+    }
+
+    pub fn show_field_editor(&mut self, field_editor: FieldEditor) {
+        self.popup
+            .show_popup(String::from("Edit field"), field_editor.to_string());
+        self.field_editor = Some(field_editor);
+        self.input_mode = InputMode::FieldEdit;
+    }
+
+    /// Apply `update` to the field editor and redraw its popup.
+    pub fn update_field_editor(&mut self, update: impl FnOnce(&mut FieldEditor)) {
+        if let Some(field_editor) = self.field_editor.as_mut() {
+            update(field_editor);
+            self.popup.message = field_editor.to_string();
+        }
+    }
+
+    pub fn hide_field_editor(&mut self) -> Option<FieldEditor> {
+        self.popup.close_popup();
+        self.input_mode = InputMode::Normal;
+        self.field_editor.take()
+    }
+
+    pub fn show_template_picker(&mut self, template_picker: TemplatePicker) {
+        self.popup.show_popup(
+            format!("New issue - {}", template_picker.repo),
+            template_picker.to_string(),
+        );
+        self.template_picker = Some(template_picker);
+        self.input_mode = InputMode::TemplatePick;
+    }
+
+    /// Apply `update` to the template picker and redraw its popup.
+    pub fn update_template_picker(&mut self, update: impl FnOnce(&mut TemplatePicker)) {
+        if let Some(template_picker) = self.template_picker.as_mut() {
+            update(template_picker);
+            self.popup.message = template_picker.to_string();
+        }
+    }
+
+    pub fn hide_template_picker(&mut self) -> Option<TemplatePicker> {
+        self.popup.close_popup();
+        self.input_mode = InputMode::Normal;
+        self.template_picker.take()
+    }
+
+    pub fn show_issue_form(&mut self, issue_form: IssueForm) {
+        self.popup.show_popup(
+            format!("New issue - {}", issue_form.repo),
+            issue_form.to_string(),
+        );
+        self.issue_form = Some(issue_form);
+        self.input_mode = InputMode::IssueForm;
+    }
+
+    /// Apply `update` to the issue form and redraw its popup.
+    pub fn update_issue_form(&mut self, update: impl FnOnce(&mut IssueForm)) {
+        if let Some(issue_form) = self.issue_form.as_mut() {
+            update(issue_form);
+            self.popup.message = issue_form.to_string();
+        }
+    }
+
+    pub fn hide_issue_form(&mut self) -> Option<IssueForm> {
+        self.popup.close_popup();
+        self.input_mode = InputMode::Normal;
+        self.issue_form.take()
+    }
+
+    pub fn show_profile_picker(&mut self, profile_picker: ProfilePicker) {
+        self.popup
+            .show_popup(String::from("Profiles"), profile_picker.to_string());
+        self.profile_picker = Some(profile_picker);
+        self.input_mode = InputMode::ProfilePick;
+    }
+
+    /// Apply `update` to the profile picker and redraw its popup.
+    pub fn update_profile_picker(&mut self, update: impl FnOnce(&mut ProfilePicker)) {
+        if let Some(profile_picker) = self.profile_picker.as_mut() {
+            update(profile_picker);
+            self.popup.message = profile_picker.to_string();
+        }
+    }
+
+    pub fn hide_profile_picker(&mut self) -> Option<ProfilePicker> {
+        self.popup.close_popup();
+        self.input_mode = InputMode::Normal;
+        self.profile_picker.take()
+    }
+
+    /// Warn about the scopes the token is missing, if any.
+    pub fn show_missing_scopes(&mut self, missing_scopes: Vec<String>) {
+        if missing_scopes.is_empty() {
+            return;
+        }
+
+        self.show_message(
+            String::from("Missing token scopes"),
+            format!(
+                "The token lacks scopes some features need:\n{}",
+                missing_scopes
+                    .iter()
+                    .map(|scope| format!("- {}", scope))
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            ),
+        );
+    }
+
     pub fn show_error(&mut self, error_message: String) {
         self.popup.show_popup(String::from("Error"), error_message);
         self.screen = Screen::Error;
         self.input_mode = InputMode::Normal;
     }
 
-    pub fn close_error(&mut self) {
+    pub fn show_message(&mut self, title: String, message: String) {
+        self.popup.show_popup(title, message);
+        self.screen = Screen::Message;
+        self.input_mode = InputMode::Normal;
+    }
+
+    /// Close an error or message popup.
+    pub fn close_message(&mut self) {
         self.popup.close_popup();
-        self.screen = Screen::Issues;
+        self.screen = self.list_screen();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forge;
+
+    fn app_state() -> AppState {
+        let (events, _) = tokio::sync::mpsc::unbounded_channel();
+
+        AppState::new(
+            Config::default(),
+            forge::connect(&Profile::default()),
+            events,
+            vec![],
+        )
+    }
+
+    fn issue(number: usize, updated_at: &str) -> Issue {
+        serde_json::from_value(serde_json::json!({
+            "html_url": format!("https://github.com/owner/repo/issues/{}", number),
+            "repository_url": "https://api.github.com/repos/owner/repo",
+            "number": number,
+            "title": "Title",
+            "body": "",
+            "user": { "login": "alice" },
+            "milestone": null,
+            "updated_at": updated_at,
+            "pull_request": null,
+        }))
+        .unwrap()
+    }
+
+    /// The numbers of the unseen issues.
+    fn unseen(app_state: &AppState) -> Vec<usize> {
+        let mut numbers = app_state
+            .unseen_issues
+            .iter()
+            .filter_map(|html_url| html_url.rsplit('/').next()?.parse().ok())
+            .collect::<Vec<usize>>();
+
+        numbers.sort();
+        numbers
+    }
+
+    #[test]
+    fn marks_new_and_updated_issues_once_refreshed() {
+        let mut app_state = app_state();
+        let repo = String::from("owner/repo");
+
+        app_state.cache_issues(
+            repo.clone(),
+            vec![
+                issue(1, "2024-01-01T00:00:00Z"),
+                issue(2, "2024-01-01T00:00:00Z"),
+            ],
+        );
+        assert!(unseen(&app_state).is_empty());
+
+        app_state.cache_issues(
+            repo,
+            vec![
+                issue(1, "2024-01-02T00:00:00Z"),
+                issue(2, "2024-01-01T00:00:00Z"),
+                issue(3, "2024-01-02T00:00:00Z"),
+            ],
+        );
+        assert_eq!(unseen(&app_state), [1, 3]);
+    }
+
+    #[test]
+    fn selecting_an_issue_marks_it_seen() {
+        let mut app_state = app_state();
+        let repo = String::from("owner/repo");
+
+        app_state.cache_issues(repo.clone(), vec![issue(1, "2024-01-01T00:00:00Z")]);
+
+        let refreshed = vec![
+            issue(1, "2024-01-02T00:00:00Z"),
+            issue(2, "2024-01-02T00:00:00Z"),
+        ];
+        app_state.cache_issues(repo, refreshed.clone());
+        assert_eq!(unseen(&app_state), [1, 2]);
+
+        // The first issue is selected when listed
+        app_state.set_issues(refreshed);
+        assert_eq!(unseen(&app_state), [2]);
+
+        app_state.next_issue();
+        assert!(unseen(&app_state).is_empty());
     }
 }
@@ -1,17 +1,34 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 pub struct Args {
-    /// Github access token
+    /// Name of the profile to use, created when it doesn't exist
+    #[arg(short, long)]
+    pub profile: Option<String>,
+
+    /// Access token of the backend of the profile
     #[arg(short, long)]
     pub token: Option<String>,
 
-    /// Github user name
+    /// User name on the backend of the profile, the login of the token when not set
     #[arg(short, long)]
     pub user_name: Option<String>,
 
     /// Print the config file path
     #[clap(short, long, action)]
     pub file_path: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Log in to Github in the browser and store the token in the profile
+    Login {
+        /// Web url of the Github Enterprise host to log in to
+        #[arg(long)]
+        host: Option<String>,
+    },
 }
@@ -0,0 +1,148 @@
+use core::fmt;
+use serde::Deserialize;
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum CheckState {
+    Success,
+    Failure,
+    Pending,
+}
+
+impl CheckState {
+    /// Map a commit status `state` onto a check state.
+    pub fn from_status(state: &str) -> Self {
+        match state {
+            "success" => Self::Success,
+            "failure" | "error" => Self::Failure,
+            _ => Self::Pending,
+        }
+    }
+
+    /// Map a check run `status` and `conclusion` onto a check state.
+    pub fn from_check_run(status: &str, conclusion: Option<&str>) -> Self {
+        if status != "completed" {
+            return Self::Pending;
+        }
+
+        match conclusion {
+            Some("success") | Some("neutral") | Some("skipped") => Self::Success,
+            Some("failure")
+            | Some("cancelled")
+            | Some("timed_out")
+            | Some("action_required")
+            | Some("startup_failure") => Self::Failure,
+            _ => Self::Pending,
+        }
+    }
+}
+
+impl fmt::Display for CheckState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Success => write!(f, "✔"),
+            Self::Failure => write!(f, "✘"),
+            Self::Pending => write!(f, "●"),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Check {
+    pub name: String,
+    pub state: CheckState,
+    pub description: Option<String>,
+    pub url: Option<String>,
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.state, self.name)?;
+
+        if let Some(description) = &self.description {
+            write!(f, " - {}", description)?;
+        }
+
+        if self.state == CheckState::Failure {
+            if let Some(url) = &self.url {
+                write!(f, " ({})", url)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The combined checks of a single commit.
+#[derive(Clone)]
+pub struct Checks {
+    pub state: CheckState,
+    pub checks: Vec<Check>,
+}
+
+impl Checks {
+    pub fn new(checks: Vec<Check>) -> Self {
+        let state = if checks
+            .iter()
+            .any(|check| check.state == CheckState::Failure)
+        {
+            CheckState::Failure
+        } else if checks
+            .iter()
+            .any(|check| check.state == CheckState::Pending)
+        {
+            CheckState::Pending
+        } else {
+            CheckState::Success
+        };
+
+        Self { state, checks }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct CombinedStatus {
+    pub statuses: Vec<CommitStatus>,
+}
+
+#[derive(Deserialize)]
+pub struct CommitStatus {
+    pub state: String,
+    pub context: String,
+    pub description: Option<String>,
+    pub target_url: Option<String>,
+}
+
+impl From<CommitStatus> for Check {
+    fn from(status: CommitStatus) -> Self {
+        Self {
+            name: status.context,
+            state: CheckState::from_status(status.state.as_str()),
+            description: status.description,
+            url: status.target_url,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct CheckRuns {
+    pub check_runs: Vec<CheckRun>,
+}
+
+#[derive(Deserialize)]
+pub struct CheckRun {
+    pub name: String,
+    pub status: String,
+    pub conclusion: Option<String>,
+    pub html_url: Option<String>,
+}
+
+impl From<CheckRun> for Check {
+    fn from(run: CheckRun) -> Self {
+        Self {
+            name: run.name,
+            state: CheckState::from_check_run(run.status.as_str(), run.conclusion.as_deref()),
+            description: None,
+            url: run.html_url,
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use serde::Deserialize;
+
+use super::user::{AuthorAssociation, User};
+
+/// A comment on an issue or pull request.
+#[derive(Deserialize, Clone)]
+pub struct Comment {
+    pub body: String,
+    pub user: User,
+    pub created_at: String,
+    #[serde(default)]
+    pub author_association: AuthorAssociation,
+}
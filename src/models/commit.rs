@@ -0,0 +1,6 @@
+#[derive(Clone)]
+pub struct Commit {
+    pub short_sha: String,
+    pub author: String,
+    pub subject: String,
+}
@@ -1,72 +1,384 @@
-use crossterm::style::Stylize;
+use anyhow::{anyhow, bail, Result};
+use core::fmt;
 use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    env,
+    ffi::OsString,
+    fs,
+    path::PathBuf,
+    process::Command,
+};
 
-use crate::reset_terminal;
+/// The service issues are tracked on.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum BackendType {
+    #[default]
+    Github,
+    Gitlab,
+    /// Gitea or its fork Forgejo
+    #[serde(alias = "forgejo")]
+    Gitea,
+    /// Markdown files in a local directory
+    Local,
+}
 
-#[derive(Serialize, Deserialize)]
-pub struct Config {
-    pub github_access_token: String,
+impl fmt::Display for BackendType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BackendType::Github => write!(f, "Github"),
+            BackendType::Gitlab => write!(f, "GitLab"),
+            BackendType::Gitea => write!(f, "Gitea"),
+            BackendType::Local => write!(f, "local"),
+        }
+    }
+}
+
+/// The account used to connect to a backend.
+///
+/// Several profiles may be configured, e.g. for work and personal accounts or a Github
+/// Enterprise host, and switched between with `--profile` or inside the app.
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct Profile {
+    pub backend: BackendType,
+    /// Url of the instance to connect to, e.g. `https://gitlab.example.com` or the Github
+    /// Enterprise host `https://github.example.com`, or the issue directory of the local backend.
+    /// The public instance of the backend is used when empty, codeberg.org for Gitea and an
+    /// `issues` directory next to the config file for the local backend.
+    pub host: String,
+    /// Access token of the backend, a private token for GitLab
+    #[serde(alias = "github_access_token")]
+    pub access_token: String,
+    /// Command printing the access token, e.g. `pass show github`
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub token_command: String,
     pub user_name: String,
+    /// Full names of repositories listed first, whether or not they have open issues
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub default_repos: Vec<String>,
+    /// The access token found by `resolve_token`, never written to the config file
+    #[serde(skip)]
+    pub token: String,
+}
+
+/// A host entry of gh's `hosts.yml`.
+#[derive(Deserialize)]
+struct GhHost {
+    /// Not set when gh keeps the token in the system keyring
+    oauth_token: Option<String>,
+}
+
+impl Profile {
+    /// Find the access token, taking the first one set of
+    /// - the `GITHUB_TOKEN` or `GH_TOKEN` environment variable
+    /// - the token gh is logged in to the host with
+    /// - the output of `token_command`
+    /// - `access_token` of the config file
+    ///
+    /// The environment and gh are only read for Github profiles.
+    pub fn resolve_token(&mut self) -> Result<()> {
+        self.resolve_token_from(|name| env::var_os(name))
+    }
+
+    /// Resolve the token like `resolve_token`, reading environment variables with `var`.
+    fn resolve_token_from(&mut self, var: impl Fn(&str) -> Option<OsString>) -> Result<()> {
+        let github_token = match self.backend {
+            BackendType::Github => ["GITHUB_TOKEN", "GH_TOKEN"]
+                .iter()
+                .find_map(|name| {
+                    var(name)
+                        .and_then(|token| token.into_string().ok())
+                        .filter(|token| !token.is_empty())
+                })
+                .or_else(|| self.gh_token(&var)),
+            _ => None,
+        };
+
+        self.token = match github_token {
+            Some(token) => token,
+            None if !self.token_command.is_empty() => run_token_command(&self.token_command)?,
+            None => self.access_token.clone(),
+        };
+
+        Ok(())
+    }
+
+    /// The token of the profile's host in gh's `hosts.yml`.
+    fn gh_token(&self, var: impl Fn(&str) -> Option<OsString>) -> Option<String> {
+        let config_dir = var("GH_CONFIG_DIR")
+            .map(PathBuf::from)
+            .or_else(|| var("XDG_CONFIG_HOME").map(|dir| PathBuf::from(dir).join("gh")))
+            .or_else(|| var("APPDATA").map(|dir| PathBuf::from(dir).join("GitHub CLI")))
+            .or_else(|| var("HOME").map(|dir| PathBuf::from(dir).join(".config/gh")))?;
+
+        let content = fs::read_to_string(config_dir.join("hosts.yml")).ok()?;
+        let mut hosts = serde_yaml::from_str::<HashMap<String, GhHost>>(content.as_str()).ok()?;
+
+        let host = match self.host.trim_end_matches('/') {
+            "" => "github.com",
+            host => host.split_once("://").map_or(host, |(_, host)| host),
+        };
+
+        hosts
+            .remove(host)?
+            .oauth_token
+            .filter(|token| !token.is_empty())
+    }
+}
+
+/// Run `command` in the shell and read the token from its output.
+fn run_token_command(command: &str) -> Result<String> {
+    let output = if cfg!(windows) {
+        Command::new("cmd").args(["/C", command]).output()
+    } else {
+        Command::new("sh").args(["-c", command]).output()
+    }
+    .map_err(|err| anyhow!("Failed to run token command `{}`: {}", command, err))?;
+
+    if !output.status.success() {
+        bail!(
+            "Token command `{}` failed with {} {}",
+            command,
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// The OAuth app the device flow of `itg login` runs against.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct LoginConfig {
+    /// Client id of an OAuth app with the device flow enabled, registered by whoever runs itg.
+    /// Github Enterprise Server hosts need an app of their own
+    pub client_id: String,
+    /// Space separated scopes requested for the token
+    pub scopes: String,
+    /// Endpoint handing out the user code, `{host}/login/device/code` when empty
+    pub device_code_url: String,
+    /// Endpoint polled for the token, `{host}/login/oauth/access_token` when empty
+    pub token_url: String,
+}
+
+impl Default for LoginConfig {
+    fn default() -> Self {
+        Self {
+            client_id: String::new(),
+            scopes: String::from("repo read:org project notifications"),
+            device_code_url: String::new(),
+            token_url: String::new(),
+        }
+    }
+}
+
+impl LoginConfig {
+    /// The configured client id, there is no default app to log in with.
+    pub fn client_id(&self) -> Result<&str> {
+        if self.client_id.is_empty() {
+            bail!(
+                "No OAuth app to log in with. Please register an OAuth app with the device flow enabled and set its client id as client_id in the [login] table of the config file (see itg --file-path)."
+            );
+        }
+
+        Ok(self.client_id.as_str())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct Config {
+    /// Name of the profile used when none is passed with `--profile`
+    pub default_profile: String,
+    /// Name of branches created from issues, `{number}` and `{title-slug}` are substituted
+    pub branch_template: String,
+    /// Seconds between background refreshes of the listed issues, off with the default `0`
+    pub refresh_interval: u64,
+    /// The OAuth app `itg login` authorizes
+    pub login: LoginConfig,
+    /// Written last as TOML tables can't be followed by plain values
+    pub profiles: BTreeMap<String, Profile>,
+    /// Name of the profile in use
+    #[serde(skip)]
+    pub active_profile: String,
+    /// Settings of config files written before profiles, moved into the `default` profile
+    #[serde(flatten, skip_serializing)]
+    legacy: Profile,
 }
 
 impl Config {
-    pub fn initialise_config(new_config: Config) -> Config {
-        let mut config: Config = confy::load("issue-tracker", None).unwrap_or_else(|err| {
-            eprintln!("{}: {}", "Error".red().bold(), err);
-            reset_terminal().unwrap_or_else(|_| panic!("Failed to reset terminal"));
-            std::process::exit(1);
-        });
+    /// Load the config file with the profile `profile_name`, the default profile when `None`,
+    /// updated with the values set in `new_profile`.
+    ///
+    /// A profile that doesn't exist yet is created.
+    pub fn initialise_config(profile_name: Option<String>, new_profile: Profile) -> Result<Config> {
+        let mut config = Config::load(profile_name)?;
 
-        Config::load_new_config(&mut config, new_config);
-        Config::check_empty_values(&config);
+        let profile = config
+            .profiles
+            .entry(config.active_profile.clone())
+            .or_default();
 
-        config
+        Config::load_new_profile(profile, new_profile);
+        profile.resolve_token()?;
+        config.store()?;
+
+        Ok(config)
     }
 
-    fn load_new_config(config: &mut Config, new_config: Config) {
-        if !new_config.github_access_token.is_empty()
-            && new_config.github_access_token != config.github_access_token
+    /// Load the config file with the profile `profile_name` active, the default profile when
+    /// `None`.
+    pub fn load(profile_name: Option<String>) -> Result<Config> {
+        let mut config: Config = confy::load("issue-tracker", None)?;
+
+        if config.profiles.is_empty() {
+            config
+                .profiles
+                .insert(String::from("default"), config.legacy.clone());
+        }
+
+        config.active_profile = profile_name.unwrap_or_else(|| config.default_profile.clone());
+
+        Ok(config)
+    }
+
+    /// The profile in use.
+    pub fn profile(&self) -> Profile {
+        self.profiles
+            .get(&self.active_profile)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn load_new_profile(profile: &mut Profile, new_profile: Profile) {
+        if !new_profile.access_token.is_empty() && new_profile.access_token != profile.access_token
         {
-            config.github_access_token = new_config.github_access_token;
+            profile.access_token = new_profile.access_token;
         }
 
-        if !new_config.user_name.is_empty() && new_config.user_name != config.user_name {
-            config.user_name = new_config.user_name;
+        if !new_profile.user_name.is_empty() && new_profile.user_name != profile.user_name {
+            profile.user_name = new_profile.user_name;
         }
+    }
 
-        confy::store("issue-tracker", None, &config).unwrap_or_else(|err| {
-            eprintln!("{}: {}", "Error".red().bold(), err);
-            reset_terminal().unwrap_or_else(|_| panic!("Failed to reset terminal"));
-            std::process::exit(1);
-        });
+    pub fn store(&self) -> Result<()> {
+        Ok(confy::store("issue-tracker", None, self)?)
     }
 
-    fn check_empty_values(config: &Config) {
-        if config.github_access_token.is_empty() {
-            eprintln!(
-                "{}: No Github access token set. Please set one with the --token (-t) flag.",
-                "Error".red().bold()
+    /// Fail when the profile in use misses a value required to connect.
+    pub fn check_empty_values(&self) -> Result<()> {
+        let profile = self.profile();
+
+        // The local backend reads issues from disk without a token
+        if profile.token.is_empty() && profile.backend != BackendType::Local {
+            bail!(
+                "No access token set for the profile {}. Please set one with the --token (-t) flag, a token_command in the config file or the GITHUB_TOKEN variable.",
+                self.active_profile
             );
-            reset_terminal().unwrap_or_else(|_| panic!("Failed to reset terminal"));
-            std::process::exit(1);
         }
 
-        if config.user_name.is_empty() {
-            eprintln!(
-                "{}: No Github user name is set. Please set one with the --user-name (-u) flag.",
-                "Error".red().bold()
+        // Other backends fill it in from the login the token belongs to
+        if profile.user_name.is_empty() && profile.backend == BackendType::Local {
+            bail!(
+                "No user name is set for the profile {}. Please set one with the --user-name (-u) flag.",
+                self.active_profile
             );
-            reset_terminal().unwrap_or_else(|_| panic!("Failed to reset terminal"));
-            std::process::exit(1);
         }
+
+        Ok(())
     }
 }
 
-impl ::std::default::Default for Config {
+impl Default for Config {
     fn default() -> Self {
         Self {
-            github_access_token: String::from(""),
-            user_name: String::from(""),
+            default_profile: String::from("default"),
+            branch_template: String::from("{number}-{title-slug}"),
+            refresh_interval: 0,
+            login: LoginConfig::default(),
+            profiles: BTreeMap::new(),
+            active_profile: String::new(),
+            legacy: Profile::default(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The token `profile` resolves to with the environment variables `vars`.
+    fn resolved(profile: &Profile, vars: &HashMap<&str, OsString>) -> Result<String> {
+        let mut profile = profile.clone();
+        profile.resolve_token_from(|name| vars.get(name).cloned())?;
+
+        Ok(profile.token)
+    }
+
+    #[test]
+    fn resolves_github_tokens_in_order_of_precedence() {
+        let gh_config = tempfile::tempdir().unwrap();
+        fs::write(
+            gh_config.path().join("hosts.yml"),
+            "github.com:\n  oauth_token: gh-token\ngithub.example.com:\n  user: alice\n",
+        )
+        .unwrap();
+
+        let mut vars = HashMap::from([
+            ("GH_CONFIG_DIR", gh_config.path().as_os_str().to_owned()),
+            ("GITHUB_TOKEN", OsString::from("github-token")),
+            ("GH_TOKEN", OsString::from("gh-env-token")),
+        ]);
+
+        let mut profile = Profile {
+            token_command: String::from("echo command-token"),
+            access_token: String::from("config-token"),
+            ..Default::default()
+        };
+
+        assert_eq!(resolved(&profile, &vars).unwrap(), "github-token");
+
+        // Empty variables count as unset
+        vars.insert("GITHUB_TOKEN", OsString::new());
+        assert_eq!(resolved(&profile, &vars).unwrap(), "gh-env-token");
+
+        vars.remove("GH_TOKEN");
+        assert_eq!(resolved(&profile, &vars).unwrap(), "gh-token");
+
+        // gh keeps no token for the host when it uses the keyring
+        profile.host = String::from("https://github.example.com/");
+        assert_eq!(resolved(&profile, &vars).unwrap(), "command-token");
+
+        profile.token_command = String::new();
+        assert_eq!(resolved(&profile, &vars).unwrap(), "config-token");
+
+        // Other backends ignore the Github sources
+        vars.insert("GITHUB_TOKEN", OsString::from("github-token"));
+        profile.backend = BackendType::Gitlab;
+        assert_eq!(resolved(&profile, &vars).unwrap(), "config-token");
+    }
+
+    #[test]
+    fn fails_on_failing_token_commands() {
+        let profile = Profile {
+            backend: BackendType::Gitea,
+            token_command: String::from("exit 3"),
+            access_token: String::from("config-token"),
+            ..Default::default()
+        };
+
+        assert!(resolved(&profile, &HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn requires_an_oauth_app_to_log_in() {
+        let mut login = LoginConfig::default();
+        assert!(login.client_id().is_err());
+
+        login.client_id = String::from("own-app");
+        assert_eq!(login.client_id().unwrap(), "own-app");
+    }
+}
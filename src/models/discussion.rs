@@ -0,0 +1,69 @@
+use core::fmt;
+use serde::Deserialize;
+
+use super::{
+    graphql::Connection,
+    user::{AuthorAssociation, User},
+};
+
+#[derive(Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Discussion {
+    pub id: String,
+    pub number: usize,
+    pub title: String,
+    pub body: String,
+    pub url: String,
+    /// `None` when the author's account has been deleted
+    pub author: Option<User>,
+    #[serde(default)]
+    pub author_association: AuthorAssociation,
+    pub category: DiscussionCategory,
+    pub is_answered: Option<bool>,
+    pub comments: Connection<DiscussionComment>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct DiscussionCategory {
+    pub name: String,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscussionComment {
+    pub id: String,
+    pub body: String,
+    pub author: Option<User>,
+    #[serde(default)]
+    pub author_association: AuthorAssociation,
+    #[serde(default)]
+    pub is_answer: bool,
+    #[serde(default)]
+    pub replies: Connection<DiscussionComment>,
+}
+
+impl DiscussionComment {
+    pub fn author_login(&self) -> &str {
+        self.author
+            .as_ref()
+            .map(|author| author.login.as_str())
+            .unwrap_or("ghost")
+    }
+}
+
+impl fmt::Display for Discussion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{}] {}{}: {}",
+            self.category.name,
+            if self.is_answered.unwrap_or(false) {
+                "✔ "
+            } else {
+                ""
+            },
+            self.number,
+            self.title
+        )
+    }
+}
@@ -2,12 +2,61 @@ use core::fmt;
 
 pub enum Errors {
     FetchRequestError,
+    SubmitRequestError,
+    NotPullRequestError,
+    EditorError,
+    PagerError,
+    EmptyBodyError,
+    EmptyTitleError,
+    NoRepositoryError,
+    UnsupportedError,
+    ProfileError,
+    InvalidTokenError,
+    MergeConflictError,
+    MergeBlockedError,
+    HeadModifiedError,
+    DeleteBranchError,
+    NotInRepositoryError,
+    DirtyWorktreeError,
+    GitError,
+    BrowserError,
 }
 
 impl fmt::Display for Errors {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Errors::FetchRequestError => write!(f, "Failed to fetch requested content."),
+            Errors::SubmitRequestError => write!(f, "Failed to submit request."),
+            Errors::NotPullRequestError => write!(f, "The selected issue is not a pull request."),
+            Errors::EditorError => write!(f, "Failed to open editor."),
+            Errors::PagerError => write!(f, "Failed to open pager."),
+            Errors::EmptyBodyError => write!(f, "A body is required but none was written."),
+            Errors::EmptyTitleError => write!(f, "A title is required but none was written."),
+            Errors::NoRepositoryError => write!(f, "Select a repository first."),
+            Errors::UnsupportedError => write!(f, "Not supported by the backend:"),
+            Errors::ProfileError => write!(f, "Failed to switch profile:"),
+            Errors::InvalidTokenError => write!(f, "The token was not accepted:"),
+            Errors::MergeConflictError => write!(
+                f,
+                "The pull request has conflicts with the base branch. Resolve them before merging."
+            ),
+            Errors::MergeBlockedError => write!(f, "Merge blocked by branch protection:"),
+            Errors::HeadModifiedError => write!(
+                f,
+                "The head branch was modified since opening the merge. Review the changes and try again."
+            ),
+            Errors::NotInRepositoryError => {
+                write!(f, "The current directory is not a clone of")
+            }
+            Errors::DirtyWorktreeError => write!(
+                f,
+                "The working tree has uncommitted changes. Commit or stash them first."
+            ),
+            Errors::GitError => write!(f, "Git failed:"),
+            Errors::BrowserError => write!(f, "Failed to open the browser:"),
+            Errors::DeleteBranchError => {
+                write!(f, "Pull request merged, but the head branch could not be deleted:")
+            }
         }
     }
 }
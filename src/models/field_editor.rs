@@ -0,0 +1,81 @@
+use core::fmt;
+
+use super::{
+    project::{FieldOption, ProjectField},
+    stateful_list::StatefulList,
+};
+
+/// The state of the popup editing a field of a project item.
+pub struct FieldEditor {
+    pub item_id: String,
+    /// The field being edited, `None` while picking the field
+    pub field: Option<ProjectField>,
+    pub fields: StatefulList<ProjectField>,
+    pub options: StatefulList<FieldOption>,
+}
+
+impl FieldEditor {
+    pub fn new(item_id: String, fields: Vec<ProjectField>) -> Self {
+        Self {
+            item_id,
+            field: None,
+            fields: StatefulList::with_items(fields),
+            options: StatefulList::with_items(vec![]),
+        }
+    }
+
+    pub fn next(&mut self) {
+        match self.field {
+            Some(_) => self.options.next(),
+            None => self.fields.next(),
+        }
+    }
+
+    pub fn previous(&mut self) {
+        match self.field {
+            Some(_) => self.options.previous(),
+            None => self.fields.previous(),
+        }
+    }
+
+    /// Pick the selected field, listing its options with a first option to clear it.
+    pub fn pick_field(&mut self) {
+        if let Some(field) = self.fields.selected_value().cloned() {
+            let mut options = vec![FieldOption {
+                id: String::new(),
+                name: String::from("Clear"),
+            }];
+            options.extend(field.options());
+
+            self.options = StatefulList::with_items(options);
+            self.field = Some(field);
+        }
+    }
+}
+
+impl fmt::Display for FieldEditor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (items, selected) = match &self.field {
+            Some(_) => (
+                self.options
+                    .items
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<String>>(),
+                self.options.selected(),
+            ),
+            None => (
+                self.fields.items.iter().map(ToString::to_string).collect(),
+                self.fields.selected(),
+            ),
+        };
+
+        for (index, item) in items.iter().enumerate() {
+            let marker = if Some(index) == selected { ">" } else { " " };
+            writeln!(f, "{} {}", marker, item)?;
+        }
+
+        writeln!(f)?;
+        write!(f, "Up / k && Down / j: select, Enter: choose, Esc: cancel")
+    }
+}
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Serialize)]
+pub struct GraphqlRequest<'a> {
+    pub query: &'a str,
+    pub variables: Value,
+}
+
+#[derive(Deserialize)]
+pub struct GraphqlResponse<T> {
+    pub data: Option<T>,
+    pub errors: Option<Vec<GraphqlError>>,
+}
+
+#[derive(Deserialize)]
+pub struct GraphqlError {
+    pub message: String,
+}
+
+/// A paginated GraphQL list, only the nodes are requested.
+#[derive(Deserialize, Clone)]
+pub struct Connection<T> {
+    pub nodes: Vec<T>,
+}
+
+impl<T> Default for Connection<T> {
+    fn default() -> Self {
+        Self { nodes: vec![] }
+    }
+}
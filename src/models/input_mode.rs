@@ -1,4 +1,10 @@
 pub enum InputMode {
     Normal,
     Editing,
+    Review,
+    Merge,
+    FieldEdit,
+    TemplatePick,
+    IssueForm,
+    ProfilePick,
 }
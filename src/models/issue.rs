@@ -1,16 +1,124 @@
 use core::fmt;
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use super::{
+    checks::Checks,
+    review::Review,
+    user::{AuthorAssociation, User},
+};
 
 #[derive(Deserialize, Clone)]
 pub struct Issue {
     pub html_url: String,
+    /// The `{owner}/{repo}` name of the repository the issue belongs to
+    #[serde(
+        rename = "repository_url",
+        deserialize_with = "repository_name_from_url"
+    )]
+    pub repository: String,
     pub number: usize,
+    /// Marks the number in references, `!` for GitLab merge requests as they are numbered apart
+    /// from issues
+    #[serde(skip, default = "default_sigil")]
+    pub sigil: char,
     pub title: String,
     pub body: String,
+    pub user: User,
+    #[serde(default)]
+    pub author_association: AuthorAssociation,
+    /// The number of comments on the issue
+    #[serde(default)]
+    pub comments: usize,
+    #[serde(default)]
+    pub labels: Vec<Label>,
+    pub milestone: Option<Milestone>,
+    /// When the issue last changed, empty if the backend doesn't tell
+    #[serde(default)]
+    pub updated_at: String,
+    /// Only present when the issue is a pull request
+    pub pull_request: Option<IssuePullRequest>,
+    /// Checks of the pull requests head commit
+    #[serde(skip)]
+    pub checks: Option<Checks>,
+    /// Reviews submitted on the pull request
+    #[serde(skip)]
+    pub reviews: Vec<Review>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct Label {
+    pub name: String,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct Milestone {
+    pub title: String,
+}
+
+/// The body of a request creating an issue.
+#[derive(Serialize)]
+pub struct NewIssue {
+    pub title: String,
+    pub body: String,
+    pub labels: Vec<String>,
+    pub assignees: Vec<String>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct IssuePullRequest {
+    pub html_url: String,
+}
+
+impl Issue {
+    pub fn is_pull_request(&self) -> bool {
+        self.pull_request.is_some()
+    }
+
+    pub fn repository_name(&self) -> &str {
+        self.repository.as_str()
+    }
+
+    /// How the issue is referenced in commit messages, e.g. `#12` or `!12`.
+    pub fn reference(&self) -> String {
+        format!("{}{}", self.sigil, self.number)
+    }
+
+    /// The number as listed, only marked when it is not an issue number.
+    fn listed_number(&self) -> String {
+        match self.sigil {
+            '#' => self.number.to_string(),
+            _ => self.reference(),
+        }
+    }
+}
+
+fn default_sigil() -> char {
+    '#'
+}
+
+/// Github links the repository of an issue as `{api}/repos/{owner}/{repo}`.
+fn repository_name_from_url<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<String, D::Error> {
+    let url = String::deserialize(deserializer)?;
+
+    Ok(url
+        .rsplit_once("/repos/")
+        .map(|(_, name)| name.to_string())
+        .unwrap_or_default())
 }
 
 impl fmt::Display for Issue {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}: {}", self.number, self.title)
+        match &self.checks {
+            Some(checks) => write!(
+                f,
+                "{} {}: {}",
+                checks.state,
+                self.listed_number(),
+                self.title
+            ),
+            None => write!(f, "{}: {}", self.listed_number(), self.title),
+        }
     }
 }
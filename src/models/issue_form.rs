@@ -0,0 +1,303 @@
+use core::fmt;
+
+use super::{
+    issue::NewIssue,
+    issue_template::{FormElement, IssueTemplate, TemplateBody},
+};
+
+/// The state of the popup writing a new issue.
+pub struct IssueForm {
+    pub repo: String,
+    pub title: String,
+    pub labels: Vec<String>,
+    pub assignees: Vec<String>,
+    pub inputs: Vec<FormInput>,
+    /// Whether the body is assembled from a section per input of an issue form
+    pub is_form: bool,
+    /// The selected row, the title followed by the inputs
+    pub selected: usize,
+}
+
+/// An input of the issue form, assembled into a section of the issue body.
+pub struct FormInput {
+    pub label: String,
+    pub kind: InputKind,
+}
+
+pub enum InputKind {
+    Text {
+        value: String,
+        /// The language the value is rendered as a code block of
+        render: Option<String>,
+    },
+    Dropdown {
+        options: Vec<String>,
+        selected: Option<usize>,
+    },
+    Checkboxes {
+        options: Vec<(String, bool)>,
+        cursor: usize,
+    },
+}
+
+impl IssueForm {
+    /// Prefill a form for an issue in `repo` from `template`.
+    pub fn new(repo: String, template: IssueTemplate) -> Self {
+        let is_form = matches!(template.body, TemplateBody::Form(_));
+
+        let inputs = match template.body {
+            TemplateBody::Markdown(body) => vec![FormInput {
+                label: String::from("Body"),
+                kind: InputKind::Text {
+                    value: body,
+                    render: None,
+                },
+            }],
+            TemplateBody::Form(elements) => elements
+                .into_iter()
+                .filter_map(|element| {
+                    let (attributes, kind) = match element {
+                        FormElement::Input { attributes }
+                        | FormElement::Textarea { attributes } => {
+                            let kind = InputKind::Text {
+                                value: attributes.value.clone(),
+                                render: attributes.render.clone(),
+                            };
+                            (attributes, kind)
+                        }
+                        FormElement::Dropdown { attributes } => {
+                            let kind = InputKind::Dropdown {
+                                options: attributes
+                                    .options
+                                    .iter()
+                                    .map(|option| option.label().to_string())
+                                    .collect(),
+                                selected: None,
+                            };
+                            (attributes, kind)
+                        }
+                        FormElement::Checkboxes { attributes } => {
+                            let kind = InputKind::Checkboxes {
+                                options: attributes
+                                    .options
+                                    .iter()
+                                    .map(|option| (option.label().to_string(), false))
+                                    .collect(),
+                                cursor: 0,
+                            };
+                            (attributes, kind)
+                        }
+                        FormElement::Markdown | FormElement::Other => return None,
+                    };
+
+                    Some(FormInput {
+                        label: attributes.label,
+                        kind,
+                    })
+                })
+                .collect(),
+        };
+
+        Self {
+            repo,
+            title: template.title,
+            labels: template.labels,
+            assignees: template.assignees,
+            inputs,
+            is_form,
+            selected: 0,
+        }
+    }
+
+    pub fn next(&mut self) {
+        self.selected = (self.selected + 1).min(self.inputs.len());
+    }
+
+    pub fn previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// The selected input, `None` while the title is selected.
+    pub fn selected_input(&mut self) -> Option<&mut FormInput> {
+        self.inputs.get_mut(self.selected.checked_sub(1)?)
+    }
+
+    /// The text of the selected row when it can be typed in.
+    pub fn selected_text(&mut self) -> Option<&mut String> {
+        if self.selected == 0 {
+            return Some(&mut self.title);
+        }
+
+        match &mut self.selected_input()?.kind {
+            InputKind::Text { value, .. } => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Type `c` into the selected row, toggling the option under the cursor of checkboxes on space.
+    pub fn type_char(&mut self, c: char) {
+        if let Some(text) = self.selected_text() {
+            text.push(c);
+        } else if let Some(FormInput {
+            kind: InputKind::Checkboxes { options, cursor },
+            ..
+        }) = self.selected_input()
+        {
+            if c == ' ' {
+                if let Some((_, checked)) = options.get_mut(*cursor) {
+                    *checked = !*checked;
+                }
+            }
+        }
+    }
+
+    pub fn backspace(&mut self) {
+        if let Some(text) = self.selected_text() {
+            text.pop();
+        }
+    }
+
+    /// Move through the options of the selected dropdown or checkboxes.
+    pub fn cycle(&mut self, forward: bool) {
+        let Some(input) = self.selected_input() else {
+            return;
+        };
+
+        match &mut input.kind {
+            InputKind::Dropdown { options, selected } if !options.is_empty() => {
+                *selected = Some(match (*selected, forward) {
+                    (None, true) => 0,
+                    (None, false) => options.len() - 1,
+                    (Some(index), true) => (index + 1) % options.len(),
+                    (Some(index), false) => (index + options.len() - 1) % options.len(),
+                });
+            }
+            InputKind::Checkboxes { options, cursor } => {
+                *cursor = if forward {
+                    (*cursor + 1).min(options.len().saturating_sub(1))
+                } else {
+                    cursor.saturating_sub(1)
+                };
+            }
+            _ => {}
+        }
+    }
+
+    /// Assemble the issue body, a section per input for issue forms.
+    pub fn body(&self) -> String {
+        if !self.is_form {
+            return match self.inputs.first() {
+                Some(FormInput {
+                    kind: InputKind::Text { value, .. },
+                    ..
+                }) => value.clone(),
+                _ => String::new(),
+            };
+        }
+
+        self.inputs
+            .iter()
+            .map(|input| {
+                let value = match &input.kind {
+                    InputKind::Text { value, .. } if value.trim().is_empty() => {
+                        String::from("_No response_")
+                    }
+                    InputKind::Text {
+                        value,
+                        render: Some(language),
+                    } => format!("```{}\n{}\n```", language, value),
+                    InputKind::Text { value, .. } => value.clone(),
+                    InputKind::Dropdown {
+                        options,
+                        selected: Some(index),
+                    } => options[*index].clone(),
+                    InputKind::Dropdown { .. } => String::from("_No response_"),
+                    InputKind::Checkboxes { options, .. } => options
+                        .iter()
+                        .map(|(label, checked)| {
+                            format!("- [{}] {}", if *checked { "X" } else { " " }, label)
+                        })
+                        .collect::<Vec<String>>()
+                        .join("\n"),
+                };
+
+                format!("### {}\n\n{}", input.label, value)
+            })
+            .collect::<Vec<String>>()
+            .join("\n\n")
+    }
+
+    pub fn request(&self) -> NewIssue {
+        NewIssue {
+            title: self.title.trim().to_string(),
+            body: self.body(),
+            labels: self.labels.clone(),
+            assignees: self.assignees.clone(),
+        }
+    }
+}
+
+impl fmt::Display for FormInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.label)?;
+
+        match &self.kind {
+            InputKind::Text { value, .. } => {
+                let mut lines = value.lines();
+                write!(f, "{}", lines.next().unwrap_or(""))?;
+
+                if lines.next().is_some() {
+                    write!(f, " …")?;
+                }
+
+                Ok(())
+            }
+            InputKind::Dropdown { options, selected } => write!(
+                f,
+                "< {} >",
+                selected
+                    .map(|index| options[index].as_str())
+                    .unwrap_or("None")
+            ),
+            InputKind::Checkboxes { options, cursor } => {
+                for (index, (label, checked)) in options.iter().enumerate() {
+                    let mark = if *checked { "x" } else { " " };
+
+                    if index == *cursor {
+                        write!(f, "<[{}] {}> ", mark, label)?;
+                    } else {
+                        write!(f, " [{}] {}  ", mark, label)?;
+                    }
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl fmt::Display for IssueForm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let marker = |index: usize| if index == self.selected { ">" } else { " " };
+
+        writeln!(f, "{} Title: {}", marker(0), self.title)?;
+
+        for (index, input) in self.inputs.iter().enumerate() {
+            writeln!(f, "{} {}", marker(index + 1), input)?;
+        }
+
+        if !self.labels.is_empty() {
+            writeln!(f, "  Labels: {}", self.labels.join(", "))?;
+        }
+
+        if !self.assignees.is_empty() {
+            writeln!(f, "  Assignees: {}", self.assignees.join(", "))?;
+        }
+
+        writeln!(f)?;
+        write!(
+            f,
+            "Up / Down: select, Ctrl-e: edit in editor, Left / Right: choose option, Space: toggle checkbox, Enter: create, Esc: cancel"
+        )
+    }
+}
@@ -0,0 +1,284 @@
+use anyhow::Result;
+use core::fmt;
+use serde::{Deserialize, Deserializer};
+
+use super::stateful_list::StatefulList;
+
+/// An issue template of a repository, either markdown with front-matter or a YAML issue form.
+#[derive(Clone)]
+pub struct IssueTemplate {
+    pub name: String,
+    pub about: String,
+    pub title: String,
+    pub labels: Vec<String>,
+    pub assignees: Vec<String>,
+    pub body: TemplateBody,
+}
+
+#[derive(Clone)]
+pub enum TemplateBody {
+    Markdown(String),
+    Form(Vec<FormElement>),
+}
+
+/// The front-matter of a markdown template, or the top level keys of an issue form.
+#[derive(Deserialize, Default)]
+struct TemplateHeader {
+    #[serde(default)]
+    name: String,
+    #[serde(default, alias = "description")]
+    about: String,
+    #[serde(default)]
+    title: String,
+    #[serde(default, deserialize_with = "string_or_list")]
+    labels: Vec<String>,
+    #[serde(default, deserialize_with = "string_or_list")]
+    assignees: Vec<String>,
+    /// Only set for issue forms
+    #[serde(default)]
+    body: Vec<FormElement>,
+}
+
+/// Split markdown starting with YAML front-matter between `---` lines into the front-matter and body.
+pub fn split_front_matter(content: &str) -> Option<(&str, &str)> {
+    let (front_matter, body) = content
+        .trim_start()
+        .strip_prefix("---")?
+        .split_once("\n---")?;
+
+    Some((
+        front_matter,
+        body.split_once('\n').map(|(_, body)| body).unwrap_or(""),
+    ))
+}
+
+/// Labels and assignees may be written as a comma separated string or a list.
+fn string_or_list<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StringOrList {
+        String(String),
+        List(Vec<String>),
+    }
+
+    Ok(match Option::<StringOrList>::deserialize(deserializer)? {
+        Some(StringOrList::String(values)) => values
+            .split(',')
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .collect(),
+        Some(StringOrList::List(values)) => values,
+        None => vec![],
+    })
+}
+
+impl IssueTemplate {
+    /// An empty template for issues that don't follow one.
+    pub fn blank() -> Self {
+        Self {
+            name: String::from("Blank issue"),
+            about: String::new(),
+            title: String::new(),
+            labels: vec![],
+            assignees: vec![],
+            body: TemplateBody::Markdown(String::new()),
+        }
+    }
+
+    /// Parse the template file `file_name`, a YAML issue form when it ends in `.yml` or `.yaml`.
+    pub fn parse(file_name: &str, content: &str) -> Result<Self> {
+        if file_name.ends_with(".yml") || file_name.ends_with(".yaml") {
+            let mut header = serde_yaml::from_str::<TemplateHeader>(content)?;
+            let form = std::mem::take(&mut header.body);
+
+            return Ok(Self::from_header(
+                file_name,
+                header,
+                TemplateBody::Form(form),
+            ));
+        }
+
+        let (header, body) = match split_front_matter(content) {
+            Some((front_matter, body)) => (
+                serde_yaml::from_str::<Option<TemplateHeader>>(front_matter)?.unwrap_or_default(),
+                body,
+            ),
+            None => (TemplateHeader::default(), content),
+        };
+
+        Ok(Self::from_header(
+            file_name,
+            header,
+            TemplateBody::Markdown(body.trim().to_string()),
+        ))
+    }
+
+    fn from_header(file_name: &str, header: TemplateHeader, body: TemplateBody) -> Self {
+        Self {
+            name: Some(header.name)
+                .filter(|name| !name.is_empty())
+                .unwrap_or(file_name.to_string()),
+            about: header.about,
+            title: header.title,
+            labels: header.labels,
+            assignees: header.assignees,
+            body,
+        }
+    }
+}
+
+impl fmt::Display for IssueTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.about.is_empty() {
+            write!(f, "{}", self.name)
+        } else {
+            write!(f, "{} - {}", self.name, self.about)
+        }
+    }
+}
+
+/// An element of a YAML issue form.
+#[derive(Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum FormElement {
+    /// Text shown to the author that is not part of the issue body
+    Markdown,
+    Input {
+        attributes: FormAttributes,
+    },
+    Textarea {
+        attributes: FormAttributes,
+    },
+    Dropdown {
+        attributes: FormAttributes,
+    },
+    Checkboxes {
+        attributes: FormAttributes,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct FormAttributes {
+    pub label: String,
+    #[serde(default)]
+    pub value: String,
+    #[serde(default)]
+    pub options: Vec<FormOption>,
+    /// The language textarea values are rendered as a code block of
+    pub render: Option<String>,
+}
+
+/// Dropdown options are plain strings, checkbox options carry a label.
+#[derive(Deserialize, Clone)]
+#[serde(untagged)]
+pub enum FormOption {
+    Label(String),
+    Checkbox { label: String },
+}
+
+impl FormOption {
+    pub fn label(&self) -> &str {
+        match self {
+            Self::Label(label) | Self::Checkbox { label } => label.as_str(),
+        }
+    }
+}
+
+/// The state of the popup picking a template for a new issue.
+pub struct TemplatePicker {
+    pub repo: String,
+    pub templates: StatefulList<IssueTemplate>,
+}
+
+impl TemplatePicker {
+    /// List the `templates` of `repo` followed by a blank issue.
+    pub fn new(repo: String, mut templates: Vec<IssueTemplate>) -> Self {
+        templates.push(IssueTemplate::blank());
+
+        Self {
+            repo,
+            templates: StatefulList::with_items(templates),
+        }
+    }
+}
+
+impl fmt::Display for TemplatePicker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, template) in self.templates.items.iter().enumerate() {
+            let marker = if Some(index) == self.templates.selected() {
+                ">"
+            } else {
+                " "
+            };
+            writeln!(f, "{} {}", marker, template)?;
+        }
+
+        writeln!(f)?;
+        write!(f, "Up / k && Down / j: select, Enter: choose, Esc: cancel")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_markdown_templates() {
+        let template = IssueTemplate::parse(
+            "bug.md",
+            "---\nname: Bug report\nabout: Something broke\ntitle: '[Bug] '\nlabels: bug, triage\nassignees:\n  - octo\n---\n\n## Steps\n",
+        )
+        .unwrap();
+
+        assert_eq!(template.name, "Bug report");
+        assert_eq!(template.about, "Something broke");
+        assert_eq!(template.title, "[Bug] ");
+        assert_eq!(template.labels, ["bug", "triage"]);
+        assert_eq!(template.assignees, ["octo"]);
+        assert!(matches!(template.body, TemplateBody::Markdown(body) if body == "## Steps"));
+    }
+
+    #[test]
+    fn names_templates_without_front_matter_after_the_file() {
+        let template = IssueTemplate::parse("feature.md", "Describe the feature").unwrap();
+
+        assert_eq!(template.name, "feature.md");
+        assert!(template.labels.is_empty());
+        assert!(
+            matches!(template.body, TemplateBody::Markdown(body) if body == "Describe the feature")
+        );
+    }
+
+    #[test]
+    fn parses_issue_forms() {
+        let template = IssueTemplate::parse(
+            "crash.yml",
+            "name: Crash\ndescription: The app crashed\nlabels: [crash]\nbody:\n  - type: markdown\n    attributes:\n      value: Thanks!\n  - type: textarea\n    attributes:\n      label: Logs\n      render: shell\n  - type: dropdown\n    attributes:\n      label: Version\n      options: ['1.0', '2.0']\n",
+        )
+        .unwrap();
+
+        assert_eq!(template.name, "Crash");
+        assert_eq!(template.about, "The app crashed");
+        assert_eq!(template.labels, ["crash"]);
+
+        let TemplateBody::Form(elements) = template.body else {
+            panic!("expected an issue form");
+        };
+        assert!(matches!(elements[0], FormElement::Markdown));
+        assert!(matches!(
+            &elements[1],
+            FormElement::Textarea { attributes } if attributes.render.as_deref() == Some("shell")
+        ));
+        assert!(matches!(
+            &elements[2],
+            FormElement::Dropdown { attributes } if attributes.options[1].label() == "2.0"
+        ));
+    }
+
+    #[test]
+    fn rejects_malformed_front_matter() {
+        assert!(IssueTemplate::parse("broken.md", "---\nlabels: [unclosed\n---\nBody").is_err());
+    }
+}
@@ -1,14 +1,24 @@
 use core::fmt;
 use std::slice::Iter;
 
-#[derive(PartialEq)]
+#[derive(PartialEq, Clone, Copy)]
 pub enum MenuItems {
     Issues,
+    Discussions,
+    Releases,
+    Actions,
+    Board,
 }
 
 impl MenuItems {
     pub fn iterator() -> Iter<'static, MenuItems> {
-        static MENU_ITEMS: [MenuItems; 1] = [MenuItems::Issues];
+        static MENU_ITEMS: [MenuItems; 5] = [
+            MenuItems::Issues,
+            MenuItems::Discussions,
+            MenuItems::Releases,
+            MenuItems::Actions,
+            MenuItems::Board,
+        ];
         MENU_ITEMS.iter()
     }
 }
@@ -17,6 +27,10 @@ impl fmt::Display for MenuItems {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Issues => write!(f, "[I]ssues"),
+            Self::Discussions => write!(f, "[D]iscussions"),
+            Self::Releases => write!(f, "R[e]leases"),
+            Self::Actions => write!(f, "[A]ctions"),
+            Self::Board => write!(f, "[B]oard"),
         }
     }
 }
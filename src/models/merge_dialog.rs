@@ -0,0 +1,67 @@
+use core::fmt;
+
+use super::pull_request::{MergeMethod, MergeRequest, PullRequest};
+
+/// The state of the merge popup.
+#[derive(Clone)]
+pub struct MergeDialog {
+    pub repo: String,
+    pub html_url: String,
+    pub pull_request: PullRequest,
+    pub method: MergeMethod,
+    pub commit_title: String,
+    pub commit_message: String,
+    pub delete_branch: bool,
+}
+
+impl MergeDialog {
+    pub fn new(repo: String, html_url: String, pull_request: PullRequest) -> Self {
+        Self {
+            commit_title: format!("{} (#{})", pull_request.title, pull_request.number),
+            commit_message: String::new(),
+            method: MergeMethod::Merge,
+            delete_branch: false,
+            repo,
+            html_url,
+            pull_request,
+        }
+    }
+
+    pub fn request(&self) -> MergeRequest {
+        // Rebase merges keep the original commits and ignore the commit details
+        let is_rebase = self.method == MergeMethod::Rebase;
+
+        MergeRequest {
+            commit_title: Some(self.commit_title.clone())
+                .filter(|title| !is_rebase && !title.trim().is_empty()),
+            commit_message: Some(self.commit_message.clone())
+                .filter(|message| !is_rebase && !message.trim().is_empty()),
+            merge_method: self.method,
+            sha: self.pull_request.head.sha.clone(),
+        }
+    }
+}
+
+impl fmt::Display for MergeDialog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Status: {}", self.pull_request.mergeability())?;
+        writeln!(f, "Method: {}", self.method)?;
+        writeln!(f, "Title: {}", self.commit_title)?;
+        writeln!(
+            f,
+            "Message: {}",
+            self.commit_message.lines().next().unwrap_or("")
+        )?;
+        writeln!(
+            f,
+            "Delete branch {}: {}",
+            self.pull_request.head.branch,
+            if self.delete_branch { "yes" } else { "no" }
+        )?;
+        writeln!(f)?;
+        write!(
+            f,
+            "Tab: method, Ctrl-e: edit message, Ctrl-d: delete branch, Enter: merge, Esc: cancel"
+        )
+    }
+}
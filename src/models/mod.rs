@@ -1,11 +1,28 @@
 pub mod app_state;
 pub mod args;
+pub mod checks;
+pub mod comment;
+pub mod commit;
 pub mod config;
+pub mod discussion;
 pub mod errors;
+pub mod field_editor;
+pub mod graphql;
 pub mod input_mode;
 pub mod issue;
+pub mod issue_form;
+pub mod issue_template;
 pub mod menu_items;
+pub mod merge_dialog;
 pub mod popup;
+pub mod profile_picker;
+pub mod project;
+pub mod pull_request;
+pub mod release;
 pub mod repository;
+pub mod review;
 pub mod screen;
+pub mod setup_wizard;
 pub mod stateful_list;
+pub mod user;
+pub mod workflow_run;
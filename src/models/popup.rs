@@ -1,4 +1,4 @@
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct Popup {
     pub message: String,
     pub title: String,
@@ -6,7 +6,8 @@ pub struct Popup {
 }
 
 impl Popup {
-    pub fn show_popup(&mut self, title: String, message: String) { self.message = message;
+    pub fn show_popup(&mut self, title: String, message: String) {
+        self.message = message;
         self.title = title;
         self.show_popup = true;
     }
@@ -17,13 +18,3 @@ impl Popup {
         self.show_popup = false;
     }
 }
-
-impl Default for Popup {
-    fn default() -> Self {
-        Self {
-            message: String::new(),
-            title: String::new(),
-            show_popup: false,
-        }
-    }
-}
@@ -0,0 +1,51 @@
+use core::fmt;
+
+use super::{config::Profile, stateful_list::StatefulList};
+
+/// The state of the popup switching between the configured profiles.
+pub struct ProfilePicker {
+    /// The name of the profile in use
+    pub active: String,
+    pub profiles: StatefulList<(String, Profile)>,
+}
+
+impl ProfilePicker {
+    /// List `profiles` with the `active` one selected.
+    pub fn new(active: String, profiles: Vec<(String, Profile)>) -> Self {
+        let mut profiles = StatefulList::with_items(profiles);
+
+        if let Some(index) = profiles.items.iter().position(|(name, _)| *name == active) {
+            profiles.state.select(Some(index));
+        }
+
+        Self { active, profiles }
+    }
+}
+
+impl fmt::Display for ProfilePicker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, (name, profile)) in self.profiles.items.iter().enumerate() {
+            let marker = if Some(index) == self.profiles.selected() {
+                ">"
+            } else {
+                " "
+            };
+            let active = if *name == self.active {
+                " (active)"
+            } else {
+                ""
+            };
+
+            write!(f, "{} {}{} - {}", marker, name, active, profile.backend)?;
+
+            if !profile.host.is_empty() {
+                write!(f, " {}", profile.host)?;
+            }
+
+            writeln!(f)?;
+        }
+
+        writeln!(f)?;
+        write!(f, "Up / k && Down / j: select, Enter: switch, Esc: cancel")
+    }
+}
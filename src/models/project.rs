@@ -0,0 +1,460 @@
+use core::fmt;
+use serde::Deserialize;
+
+use super::{graphql::Connection, stateful_list::StatefulList};
+
+#[derive(Deserialize, Clone)]
+pub struct Project {
+    pub id: String,
+    pub title: String,
+    pub fields: Connection<ProjectField>,
+    pub items: Connection<ProjectItem>,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(tag = "__typename")]
+pub enum ProjectField {
+    #[serde(rename = "ProjectV2SingleSelectField")]
+    SingleSelect {
+        id: String,
+        name: String,
+        options: Vec<FieldOption>,
+    },
+    #[serde(rename = "ProjectV2IterationField")]
+    Iteration {
+        id: String,
+        name: String,
+        configuration: IterationConfiguration,
+    },
+    /// Field types that cannot be edited from the board
+    #[serde(other)]
+    Other,
+}
+
+impl ProjectField {
+    pub fn id(&self) -> Option<&str> {
+        match self {
+            Self::SingleSelect { id, .. } | Self::Iteration { id, .. } => Some(id.as_str()),
+            Self::Other => None,
+        }
+    }
+
+    /// The values the field can be set to.
+    pub fn options(&self) -> Vec<FieldOption> {
+        match self {
+            Self::SingleSelect { options, .. } => options.clone(),
+            Self::Iteration { configuration, .. } => configuration
+                .iterations
+                .iter()
+                .map(|iteration| FieldOption {
+                    id: iteration.id.clone(),
+                    name: iteration.title.clone(),
+                })
+                .collect(),
+            Self::Other => vec![],
+        }
+    }
+}
+
+impl fmt::Display for ProjectField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SingleSelect { name, .. } | Self::Iteration { name, .. } => write!(f, "{}", name),
+            Self::Other => Ok(()),
+        }
+    }
+}
+
+#[derive(Deserialize, Clone)]
+pub struct FieldOption {
+    pub id: String,
+    pub name: String,
+}
+
+impl fmt::Display for FieldOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+#[derive(Deserialize, Clone)]
+pub struct IterationConfiguration {
+    pub iterations: Vec<Iteration>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct Iteration {
+    pub id: String,
+    pub title: String,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectItem {
+    pub id: String,
+    /// `None` when the item is not visible to the user
+    pub content: Option<ItemContent>,
+    pub field_values: Connection<FieldValue>,
+}
+
+impl ProjectItem {
+    /// The option or iteration id the item has for `field_id`.
+    pub fn value(&self, field_id: &str) -> Option<&str> {
+        self.field_values
+            .nodes
+            .iter()
+            .find_map(|value| match value {
+                FieldValue::SingleSelect {
+                    option_id: Some(option_id),
+                    field,
+                } if field.id == field_id => Some(option_id.as_str()),
+                FieldValue::Iteration {
+                    iteration_id,
+                    field,
+                } if field.id == field_id => Some(iteration_id.as_str()),
+                _ => None,
+            })
+    }
+
+    /// Set or clear the value the item has for `field`.
+    pub fn set_value(&mut self, field: &ProjectField, value: Option<&str>) {
+        let Some(field_id) = field.id() else {
+            return;
+        };
+
+        self.field_values.nodes.retain(|existing| match existing {
+            FieldValue::SingleSelect { field, .. } | FieldValue::Iteration { field, .. } => {
+                field.id != field_id
+            }
+            FieldValue::Other => true,
+        });
+
+        let Some(value) = value else {
+            return;
+        };
+
+        let field_ref = FieldRef {
+            id: field_id.to_string(),
+        };
+
+        self.field_values.nodes.push(match field {
+            ProjectField::Iteration { .. } => FieldValue::Iteration {
+                iteration_id: value.to_string(),
+                field: field_ref,
+            },
+            _ => FieldValue::SingleSelect {
+                option_id: Some(value.to_string()),
+                field: field_ref,
+            },
+        });
+    }
+}
+
+impl fmt::Display for ProjectItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.content {
+            Some(ItemContent {
+                title,
+                number: Some(number),
+                ..
+            }) => write!(f, "#{} {}", number, title),
+            Some(ItemContent { title, .. }) => write!(f, "{}", title),
+            None => write!(f, "Restricted item"),
+        }
+    }
+}
+
+/// The issue, pull request or draft issue behind a project item.
+#[derive(Deserialize, Clone)]
+pub struct ItemContent {
+    pub title: String,
+    pub number: Option<usize>,
+    pub url: Option<String>,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(tag = "__typename")]
+pub enum FieldValue {
+    #[serde(rename = "ProjectV2ItemFieldSingleSelectValue")]
+    SingleSelect {
+        #[serde(rename = "optionId")]
+        option_id: Option<String>,
+        field: FieldRef,
+    },
+    #[serde(rename = "ProjectV2ItemFieldIterationValue")]
+    Iteration {
+        #[serde(rename = "iterationId")]
+        iteration_id: String,
+        field: FieldRef,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct FieldRef {
+    pub id: String,
+}
+
+/// A column of the board, holding the items with one status option.
+pub struct Column {
+    pub name: String,
+    /// `None` for the column of items without a status or with a status that no longer exists
+    pub option_id: Option<String>,
+    pub items: StatefulList<ProjectItem>,
+}
+
+/// A project laid out as columns of its status field.
+pub struct Board {
+    pub project: Project,
+    pub columns: Vec<Column>,
+    pub selected_column: usize,
+}
+
+impl Board {
+    pub fn new(project: Project) -> Self {
+        let mut board = Self {
+            project,
+            columns: vec![],
+            selected_column: 0,
+        };
+
+        board.build_columns();
+
+        board
+    }
+
+    /// The single select field the board is laid out by, preferring one named "Status".
+    pub fn status_field(&self) -> Option<&ProjectField> {
+        let mut single_selects = self
+            .project
+            .fields
+            .nodes
+            .iter()
+            .filter(|field| matches!(field, ProjectField::SingleSelect { .. }));
+
+        single_selects
+            .clone()
+            .find(|field| field.to_string() == "Status")
+            .or_else(|| single_selects.next())
+    }
+
+    /// The fields of the project that can be edited from the board.
+    pub fn editable_fields(&self) -> Vec<ProjectField> {
+        self.project
+            .fields
+            .nodes
+            .iter()
+            .filter(|field| field.id().is_some())
+            .cloned()
+            .collect()
+    }
+
+    /// Sort the project items into a column per status option.
+    fn build_columns(&mut self) {
+        let (field_id, options) = match self.status_field() {
+            Some(field) => (field.id().unwrap_or("").to_string(), field.options()),
+            None => (String::new(), vec![]),
+        };
+
+        let known_ids: Vec<String> = options.iter().map(|option| option.id.clone()).collect();
+
+        let mut columns = vec![(String::from("No Status"), None)];
+        columns.extend(
+            options
+                .into_iter()
+                .map(|option| (option.name, Some(option.id))),
+        );
+
+        let previous = std::mem::take(&mut self.columns);
+
+        self.columns = columns
+            .into_iter()
+            .map(|(name, option_id)| {
+                let items = self
+                    .project
+                    .items
+                    .nodes
+                    .iter()
+                    .filter(|item| {
+                        // Items of deleted or renamed options would otherwise fit no column
+                        let value = item
+                            .value(field_id.as_str())
+                            .filter(|value| known_ids.iter().any(|id| id == value));
+
+                        value == option_id.as_deref()
+                    })
+                    .cloned()
+                    .collect();
+
+                let mut items = StatefulList::with_items(items);
+
+                // Keep the selection of columns that existed before
+                if let Some(column) = previous.iter().find(|column| column.option_id == option_id) {
+                    let selected = column.items.selected().unwrap_or(0);
+                    items
+                        .state
+                        .select(Some(selected.min(items.items.len().saturating_sub(1))));
+                }
+
+                Column {
+                    name,
+                    option_id,
+                    items,
+                }
+            })
+            .collect();
+
+        self.selected_column = self
+            .selected_column
+            .min(self.columns.len().saturating_sub(1));
+    }
+
+    pub fn selected_item(&self) -> Option<&ProjectItem> {
+        self.columns
+            .get(self.selected_column)?
+            .items
+            .selected_value()
+    }
+
+    pub fn next_column(&mut self) {
+        self.selected_column = (self.selected_column + 1).min(self.columns.len().saturating_sub(1));
+    }
+
+    pub fn previous_column(&mut self) {
+        self.selected_column = self.selected_column.saturating_sub(1);
+    }
+
+    pub fn next_item(&mut self) {
+        if let Some(column) = self.columns.get_mut(self.selected_column) {
+            column.items.next();
+        }
+    }
+
+    pub fn previous_item(&mut self) {
+        if let Some(column) = self.columns.get_mut(self.selected_column) {
+            column.items.previous();
+        }
+    }
+
+    /// The option id of the column `offset` columns away from the selected one.
+    pub fn neighbour_column(&self, offset: isize) -> Option<(usize, Option<String>)> {
+        let index = self.selected_column.checked_add_signed(offset)?;
+
+        self.columns
+            .get(index)
+            .map(|column| (index, column.option_id.clone()))
+    }
+
+    /// Set the value of `field` on the item with `item_id` and re-sort the columns.
+    pub fn set_item_value(&mut self, item_id: &str, field: &ProjectField, value: Option<&str>) {
+        if let Some(item) = self
+            .project
+            .items
+            .nodes
+            .iter_mut()
+            .find(|item| item.id == item_id)
+        {
+            item.set_value(field, value);
+        }
+
+        self.build_columns();
+    }
+
+    /// Select the item with `item_id` in the column it is sorted into.
+    pub fn follow_item(&mut self, item_id: &str) {
+        if let Some(index) = self
+            .columns
+            .iter()
+            .position(|column| column.items.items.iter().any(|item| item.id == item_id))
+        {
+            self.select_item(index, item_id);
+        }
+    }
+
+    /// Follow the item with `item_id` to column `index`, selecting it there.
+    pub fn select_item(&mut self, index: usize, item_id: &str) {
+        self.selected_column = index.min(self.columns.len().saturating_sub(1));
+
+        if let Some(column) = self.columns.get_mut(self.selected_column) {
+            let position = column
+                .items
+                .items
+                .iter()
+                .position(|item| item.id == item_id);
+            column.items.state.select(position);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn item(id: &str, option_id: Option<&str>) -> serde_json::Value {
+        let field_values = match option_id {
+            Some(option_id) => json!([{
+                "__typename": "ProjectV2ItemFieldSingleSelectValue",
+                "optionId": option_id,
+                "field": { "id": "status" }
+            }]),
+            None => json!([]),
+        };
+
+        json!({
+            "id": id,
+            "content": { "title": id, "number": null, "url": null },
+            "fieldValues": { "nodes": field_values }
+        })
+    }
+
+    fn board() -> Board {
+        let project = json!({
+            "id": "project",
+            "title": "Planning",
+            "fields": { "nodes": [{
+                "__typename": "ProjectV2SingleSelectField",
+                "id": "status",
+                "name": "Status",
+                "options": [{ "id": "todo", "name": "Todo" }, { "id": "done", "name": "Done" }]
+            }] },
+            "items": { "nodes": [
+                item("planned", Some("todo")),
+                item("unsorted", None),
+                item("orphaned", Some("deleted")),
+            ] }
+        });
+
+        Board::new(serde_json::from_value(project).unwrap())
+    }
+
+    fn column_items(board: &Board, name: &str) -> Vec<String> {
+        board
+            .columns
+            .iter()
+            .find(|column| column.name == name)
+            .unwrap()
+            .items
+            .items
+            .iter()
+            .map(|item| item.id.clone())
+            .collect()
+    }
+
+    #[test]
+    fn sorts_items_into_status_columns() {
+        let board = board();
+
+        assert_eq!(column_items(&board, "Todo"), ["planned"]);
+        assert!(column_items(&board, "Done").is_empty());
+    }
+
+    #[test]
+    fn keeps_items_of_deleted_options_in_the_no_status_column() {
+        assert_eq!(
+            column_items(&board(), "No Status"),
+            ["unsorted", "orphaned"]
+        );
+    }
+}
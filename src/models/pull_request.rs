@@ -0,0 +1,86 @@
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Clone)]
+pub struct PullRequest {
+    pub number: usize,
+    pub title: String,
+    pub head: PullRequestHead,
+    /// `None` while Github is still computing mergeability
+    pub mergeable: Option<bool>,
+    pub mergeable_state: String,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct PullRequestHead {
+    pub sha: String,
+    #[serde(rename = "ref")]
+    pub branch: String,
+    /// `None` when the head repository has been deleted
+    pub repo: Option<PullRequestRepository>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct PullRequestRepository {
+    pub full_name: String,
+}
+
+impl PullRequest {
+    /// A short description of whether the pull request can be merged.
+    pub fn mergeability(&self) -> &str {
+        match (self.mergeable, self.mergeable_state.as_str()) {
+            (None, _) => "Mergeability is still being checked",
+            (Some(false), _) | (_, "dirty") => "Conflicts with the base branch",
+            (_, "blocked") => "Blocked by branch protection",
+            (_, "behind") => "Head branch is behind the base branch",
+            (_, "unstable") => "Mergeable with failing checks",
+            (_, "draft") => "Draft pull request",
+            _ => "Mergeable",
+        }
+    }
+}
+
+#[derive(Serialize, PartialEq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum MergeMethod {
+    Merge,
+    Squash,
+    Rebase,
+}
+
+impl MergeMethod {
+    pub fn next(&self) -> Self {
+        match self {
+            Self::Merge => Self::Squash,
+            Self::Squash => Self::Rebase,
+            Self::Rebase => Self::Merge,
+        }
+    }
+}
+
+impl fmt::Display for MergeMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Merge => write!(f, "Create a merge commit"),
+            Self::Squash => write!(f, "Squash and merge"),
+            Self::Rebase => write!(f, "Rebase and merge"),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct MergeRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit_title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit_message: Option<String>,
+    pub merge_method: MergeMethod,
+    /// The head commit the pull request must still be at to merge
+    pub sha: String,
+}
+
+#[derive(Deserialize)]
+pub struct MergeResult {
+    pub merged: bool,
+    pub message: String,
+}
@@ -0,0 +1,39 @@
+use core::fmt;
+use serde::Deserialize;
+
+#[derive(Deserialize, Clone)]
+pub struct Release {
+    pub html_url: String,
+    pub tag_name: String,
+    pub name: Option<String>,
+    pub body: Option<String>,
+    pub draft: bool,
+    pub prerelease: bool,
+    /// `None` for draft releases
+    pub published_at: Option<String>,
+}
+
+impl fmt::Display for Release {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.tag_name)?;
+
+        if let Some(name) = self.name.as_ref().filter(|name| !name.is_empty()) {
+            write!(f, " - {}", name)?;
+        }
+
+        if self.draft {
+            write!(f, " [draft]")?;
+        }
+
+        if self.prerelease {
+            write!(f, " [pre-release]")?;
+        }
+
+        if let Some(published_at) = &self.published_at {
+            // Only show the date of the timestamp
+            write!(f, " ({})", published_at.split('T').next().unwrap_or(""))?;
+        }
+
+        Ok(())
+    }
+}
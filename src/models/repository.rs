@@ -1,11 +1,39 @@
 use core::fmt;
 use serde::Deserialize;
 
-#[derive(Deserialize, Clone)]
+#[derive(Deserialize, Clone, Default)]
 pub struct Repository {
     pub name: String,
     pub full_name: String,
-    pub open_issues_count: isize
+    pub open_issues_count: isize,
+    #[serde(default)]
+    pub default_branch: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub topics: Vec<String>,
+    #[serde(default)]
+    pub stargazers_count: usize,
+    #[serde(default)]
+    pub forks_count: usize,
+    pub license: Option<License>,
+    pub pushed_at: Option<String>,
+    /// Only the name is known, the details are fetched once the overview is shown
+    #[serde(skip)]
+    pub placeholder: bool,
+}
+
+impl Repository {
+    /// Create a repository from its `{owner}/{repo}` name.
+    pub fn from_full_name(full_name: &str) -> Self {
+        let (_, name) = full_name.split_once('/').unwrap_or(("", ""));
+
+        Self {
+            full_name: String::from(full_name),
+            name: String::from(name),
+            placeholder: true,
+            ..Default::default()
+        }
+    }
 }
 
 impl fmt::Display for Repository {
@@ -13,3 +41,8 @@ impl fmt::Display for Repository {
         write!(f, "{}", self.full_name)
     }
 }
+
+#[derive(Deserialize, Clone)]
+pub struct License {
+    pub name: String,
+}
@@ -0,0 +1,63 @@
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+use super::user::User;
+
+#[derive(Deserialize, Clone)]
+pub struct Review {
+    pub user: User,
+    pub body: String,
+    pub state: String,
+    pub submitted_at: Option<String>,
+}
+
+impl fmt::Display for Review {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = match self.state.as_str() {
+            "APPROVED" => "approved",
+            "CHANGES_REQUESTED" => "requested changes",
+            "COMMENTED" => "commented",
+            "DISMISSED" => "dismissed",
+            _ => "pending",
+        };
+
+        write!(f, "{} {}", self.user.login, state)?;
+
+        if let Some(submitted_at) = &self.submitted_at {
+            write!(f, " on {}", submitted_at)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Clone, Copy)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ReviewEvent {
+    Approve,
+    RequestChanges,
+    Comment,
+}
+
+impl ReviewEvent {
+    /// Whether Github requires a review body for the event.
+    pub fn requires_body(&self) -> bool {
+        !matches!(self, Self::Approve)
+    }
+}
+
+impl fmt::Display for ReviewEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Approve => write!(f, "Approve"),
+            Self::RequestChanges => write!(f, "Request changes"),
+            Self::Comment => write!(f, "Comment"),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct NewReview {
+    pub body: String,
+    pub event: ReviewEvent,
+}
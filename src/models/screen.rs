@@ -2,5 +2,14 @@
 pub enum Screen {
     Issues,
     Repositories,
+    Checks,
+    /// The comments in the preview pane
+    Comments,
+    Discussions,
+    Releases,
+    Runs,
+    Jobs,
+    Board,
     Error,
+    Message,
 }
@@ -0,0 +1,97 @@
+use core::fmt;
+
+use super::config::{BackendType, Profile};
+
+/// The state of the first-run setup asking for the values a profile is missing.
+pub struct SetupWizard {
+    pub profile_name: String,
+    pub backend: BackendType,
+    pub host: String,
+    pub token: String,
+    pub user_name: String,
+    /// The selected row, the host, token and user name
+    pub selected: usize,
+    /// Why the profile can't be used yet
+    pub message: String,
+}
+
+impl SetupWizard {
+    /// Prefill the setup of `profile` named `profile_name`.
+    pub fn new(profile_name: String, profile: &Profile, message: String) -> Self {
+        Self {
+            profile_name,
+            backend: profile.backend,
+            host: profile.host.clone(),
+            token: profile.token.clone(),
+            user_name: profile.user_name.clone(),
+            selected: 0,
+            message,
+        }
+    }
+
+    pub fn next(&mut self) {
+        self.selected = (self.selected + 1).min(2);
+    }
+
+    pub fn previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    fn selected_text(&mut self) -> &mut String {
+        match self.selected {
+            0 => &mut self.host,
+            1 => &mut self.token,
+            _ => &mut self.user_name,
+        }
+    }
+
+    pub fn type_char(&mut self, c: char) {
+        self.selected_text().push(c);
+    }
+
+    pub fn backspace(&mut self) {
+        self.selected_text().pop();
+    }
+
+    /// `profile` with the values entered.
+    pub fn apply(&self, profile: &Profile) -> Profile {
+        let token = self.token.trim().to_string();
+
+        Profile {
+            host: self.host.trim().to_string(),
+            // Only a token typed in is written to the config file, not one from the environment
+            access_token: if token == profile.token {
+                profile.access_token.clone()
+            } else {
+                token.clone()
+            },
+            token,
+            user_name: self.user_name.trim().to_string(),
+            ..profile.clone()
+        }
+    }
+}
+
+impl fmt::Display for SetupWizard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let marker = |index: usize| if index == self.selected { ">" } else { " " };
+
+        writeln!(
+            f,
+            "Set up the {} profile {}. Leave the host empty for the public instance and the user name empty to use the one of the token.",
+            self.backend, self.profile_name
+        )?;
+        writeln!(f)?;
+        writeln!(f, "{} Host: {}", marker(0), self.host)?;
+        writeln!(f, "{} Token: {}", marker(1), "*".repeat(self.token.len()))?;
+        writeln!(f, "{} User name: {}", marker(2), self.user_name)?;
+
+        if !self.message.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "{}", self.message)?;
+        }
+
+        writeln!(f)?;
+        write!(f, "Up / Down: select, Enter: save, Esc: quit")
+    }
+}
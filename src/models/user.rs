@@ -0,0 +1,113 @@
+use core::fmt;
+use serde::Deserialize;
+
+#[derive(Deserialize, Clone)]
+pub struct User {
+    pub login: String,
+}
+
+/// The user a token belongs to.
+pub struct AuthenticatedUser {
+    pub login: String,
+    /// The OAuth scopes of the token, `None` when the backend doesn't report them
+    pub scopes: Option<Vec<String>>,
+}
+
+impl AuthenticatedUser {
+    /// The scopes the token is missing for features of the app, with what they are needed for.
+    pub fn missing_scopes(&self) -> Vec<String> {
+        let Some(scopes) = &self.scopes else {
+            return vec![];
+        };
+        let has = |scope: &str| scopes.iter().any(|granted| granted == scope);
+
+        let mut missing = vec![];
+
+        if !has("repo") {
+            missing.push(String::from(
+                "repo: issues of private repositories, merging pull requests and workflow runs",
+            ));
+        }
+
+        if !has("project") {
+            missing.push(String::from(
+                "project: moving items and editing fields on the project board",
+            ));
+        }
+
+        missing
+    }
+}
+
+/// The public profile of a Github user.
+#[derive(Deserialize, Clone)]
+pub struct UserProfile {
+    pub login: String,
+    pub name: Option<String>,
+    pub company: Option<String>,
+    pub bio: Option<String>,
+}
+
+/// How the author of an issue, discussion or comment relates to its repository.
+#[derive(Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AuthorAssociation {
+    Owner,
+    Member,
+    Collaborator,
+    Contributor,
+    FirstTimeContributor,
+    FirstTimer,
+    Mannequin,
+    #[default]
+    #[serde(other)]
+    None,
+}
+
+impl fmt::Display for AuthorAssociation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthorAssociation::Owner => write!(f, "owner"),
+            AuthorAssociation::Member => write!(f, "member"),
+            AuthorAssociation::Collaborator => write!(f, "collaborator"),
+            AuthorAssociation::Contributor => write!(f, "contributor"),
+            AuthorAssociation::FirstTimeContributor => write!(f, "first-time contributor"),
+            AuthorAssociation::FirstTimer => write!(f, "first-time Github user"),
+            AuthorAssociation::Mannequin => write!(f, "mannequin"),
+            AuthorAssociation::None => write!(f, "no association"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(scopes: Option<&[&str]>) -> AuthenticatedUser {
+        AuthenticatedUser {
+            login: String::from("alice"),
+            scopes: scopes.map(|scopes| scopes.iter().map(|scope| scope.to_string()).collect()),
+        }
+    }
+
+    #[test]
+    fn lists_missing_scopes() {
+        let missing = |scopes| {
+            user(scopes)
+                .missing_scopes()
+                .iter()
+                .map(|missing| missing.split_once(':').unwrap().0.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert!(missing(Some(&["repo", "project", "read:org"])).is_empty());
+        assert_eq!(missing(Some(&["repo", "read:project"])), ["project"]);
+        assert_eq!(missing(Some(&["public_repo", "project"])), ["repo"]);
+        assert_eq!(missing(Some(&[])), ["repo", "project"]);
+    }
+
+    #[test]
+    fn misses_no_scopes_when_the_backend_does_not_report_them() {
+        assert!(user(None).missing_scopes().is_empty());
+    }
+}
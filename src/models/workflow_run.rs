@@ -0,0 +1,149 @@
+use core::fmt;
+use serde::Deserialize;
+
+use super::checks::CheckState;
+use crate::timestamp;
+
+#[derive(Deserialize)]
+pub struct WorkflowRuns {
+    pub workflow_runs: Vec<WorkflowRun>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct WorkflowRun {
+    pub id: u64,
+    pub name: Option<String>,
+    pub head_branch: Option<String>,
+    pub event: String,
+    pub status: Option<String>,
+    pub conclusion: Option<String>,
+    pub html_url: String,
+    pub run_started_at: Option<String>,
+    pub updated_at: String,
+}
+
+impl WorkflowRun {
+    pub fn state(&self) -> CheckState {
+        CheckState::from_check_run(
+            self.status.as_deref().unwrap_or(""),
+            self.conclusion.as_deref(),
+        )
+    }
+}
+
+impl fmt::Display for WorkflowRun {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} on {} ({}) - {}",
+            self.state(),
+            self.name.as_deref().unwrap_or("Workflow"),
+            self.head_branch.as_deref().unwrap_or("-"),
+            self.event,
+            self.conclusion
+                .as_deref()
+                .or(self.status.as_deref())
+                .unwrap_or("unknown"),
+        )?;
+
+        if self.status.as_deref() == Some("completed") {
+            if let Some(duration) = self
+                .run_started_at
+                .as_deref()
+                .and_then(|started_at| duration(started_at, self.updated_at.as_str()))
+            {
+                write!(f, " in {}", duration)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+pub struct Jobs {
+    pub jobs: Vec<Job>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct Job {
+    pub id: u64,
+    pub name: String,
+    pub status: String,
+    pub conclusion: Option<String>,
+    pub html_url: Option<String>,
+    pub started_at: Option<String>,
+    pub completed_at: Option<String>,
+    #[serde(default)]
+    pub steps: Vec<Step>,
+}
+
+impl Job {
+    pub fn state(&self) -> CheckState {
+        CheckState::from_check_run(self.status.as_str(), self.conclusion.as_deref())
+    }
+}
+
+impl fmt::Display for Job {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.state(), self.name)?;
+
+        if let (Some(started_at), Some(completed_at)) = (&self.started_at, &self.completed_at) {
+            if let Some(duration) = duration(started_at, completed_at) {
+                write!(f, " in {}", duration)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Deserialize, Clone)]
+pub struct Step {
+    pub number: usize,
+    pub name: String,
+    pub status: String,
+    pub conclusion: Option<String>,
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {}. {}",
+            CheckState::from_check_run(self.status.as_str(), self.conclusion.as_deref()),
+            self.number,
+            self.name
+        )
+    }
+}
+
+/// The human readable time between two Github timestamps, e.g. `3m 12s`.
+fn duration(from: &str, to: &str) -> Option<String> {
+    let seconds = timestamp::parse(to)? - timestamp::parse(from)?;
+
+    if seconds < 0 {
+        return None;
+    }
+
+    Some(match seconds {
+        0..=59 => format!("{}s", seconds),
+        60..=3599 => format!("{}m {}s", seconds / 60, seconds % 60),
+        _ => format!("{}h {}m", seconds / 3600, (seconds % 3600) / 60),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_durations() {
+        let duration = |to| duration("2024-02-29T23:59:00Z", to);
+
+        assert_eq!(duration("2024-02-29T23:59:42Z").as_deref(), Some("42s"));
+        assert_eq!(duration("2024-03-01T00:02:12Z").as_deref(), Some("3m 12s"));
+        assert_eq!(duration("2024-03-01T02:09:00Z").as_deref(), Some("2h 10m"));
+        assert_eq!(duration("2024-02-29T23:58:00Z"), None);
+    }
+}
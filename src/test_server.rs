@@ -0,0 +1,79 @@
+use serde_json::{json, Value};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
+
+/// Answer requests for the paths in `routes` with their JSON body on a local port, and all
+/// others with a 404 carrying `not_found` as its message. Returns the url of the server.
+///
+/// A path listed more than once is answered with its bodies in turn, repeating the last one.
+pub async fn serve(routes: Vec<(&'static str, Value)>, not_found: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    tokio::spawn(async move {
+        let mut answered = vec![0; routes.len()];
+
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let request = read_request(&mut stream).await;
+            let path = request.split(' ').nth(1).unwrap_or_default();
+
+            let matching = (0..routes.len())
+                .filter(|&index| routes[index].0 == path)
+                .collect::<Vec<usize>>();
+            let next = matching
+                .iter()
+                .find(|&&index| answered[index] == 0)
+                .or(matching.last());
+
+            let (status, body) = match next {
+                Some(&index) => {
+                    answered[index] += 1;
+                    ("200 OK", routes[index].1.to_string())
+                }
+                None => ("404 Not Found", json!({ "message": not_found }).to_string()),
+            };
+
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes()).await;
+        }
+    });
+
+    url
+}
+
+/// Read the head of a request and its body, if it announces one.
+async fn read_request(stream: &mut tokio::net::TcpStream) -> String {
+    let mut request = vec![];
+    let mut buffer = [0; 1024];
+
+    loop {
+        let text = String::from_utf8_lossy(&request);
+
+        if let Some((head, body)) = text.split_once("\r\n\r\n") {
+            let length = head
+                .lines()
+                .find_map(|line| {
+                    let (name, value) = line.split_once(':')?;
+                    name.eq_ignore_ascii_case("content-length")
+                        .then(|| value.trim().parse::<usize>().ok())?
+                })
+                .unwrap_or(0);
+
+            if body.len() >= length {
+                return text.to_string();
+            }
+        }
+
+        match stream.read(&mut buffer).await {
+            Ok(0) | Err(_) => return String::from_utf8_lossy(&request).to_string(),
+            Ok(read) => request.extend_from_slice(&buffer[..read]),
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Seconds since the unix epoch of a `YYYY-MM-DDTHH:MM:SSZ` timestamp.
+pub fn parse(timestamp: &str) -> Option<i64> {
+    let (date, time) = timestamp.trim_end_matches('Z').split_once('T')?;
+
+    let mut date = date.splitn(3, '-').map(|part| part.parse::<i64>().ok());
+    let (year, month, day) = (date.next()??, date.next()??, date.next()??);
+
+    let mut time = time.splitn(3, ':').map(|part| part.parse::<i64>().ok());
+    let (hour, minute, second) = (time.next()??, time.next()??, time.next()??);
+
+    Some(days_from_civil(year, month, day) * 86_400 + hour * 3_600 + minute * 60 + second)
+}
+
+/// The timestamp `seconds` after the unix epoch.
+pub fn format(seconds: i64) -> String {
+    let (days, time) = (seconds.div_euclid(86_400), seconds.rem_euclid(86_400));
+    let (year, month, day) = civil_from_days(days);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        time / 3_600,
+        time % 3_600 / 60,
+        time % 60
+    )
+}
+
+/// The current time as a timestamp.
+pub fn now() -> String {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+
+    format(seconds as i64)
+}
+
+// Dates convert to and from days since the epoch as in
+// http://howardhinnant.github.io/date_algorithms.html
+
+/// Days since the epoch of a civil date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The civil date `days` after the epoch, as year, month and day.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+
+    (year_of_era + era * 400 + i64::from(month <= 2), month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_timestamps_to_epoch_seconds() {
+        assert_eq!(parse("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(parse("1969-12-31T23:59:59Z"), Some(-1));
+        assert_eq!(parse("2000-03-01T00:00:00Z"), Some(951868800));
+        // Leap day
+        assert_eq!(parse("2024-02-29T12:34:56Z"), Some(1709210096));
+    }
+
+    #[test]
+    fn rejects_malformed_timestamps() {
+        assert_eq!(parse(""), None);
+        assert_eq!(parse("2024-02-29"), None);
+        assert_eq!(parse("2024-02-29T12:34Z"), None);
+        assert_eq!(parse("2024-xx-29T12:34:56Z"), None);
+    }
+
+    #[test]
+    fn formats_epoch_seconds() {
+        assert_eq!(format(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format(-1), "1969-12-31T23:59:59Z");
+        assert_eq!(format(1709210096), "2024-02-29T12:34:56Z");
+
+        for seconds in [951868799, 951868800, 4102444800] {
+            assert_eq!(parse(format(seconds).as_str()), Some(seconds));
+        }
+    }
+}
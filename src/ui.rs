@@ -9,26 +9,18 @@ use tui::{
 };
 
 use crate::{
-    models::{popup::Popup, screen::Screen, stateful_list::StatefulList},
+    events::Load,
+    models::{
+        comment::Comment, commit::Commit, discussion::Discussion, issue::Issue, popup::Popup,
+        repository::Repository, screen::Screen, setup_wizard::SetupWizard,
+        stateful_list::StatefulList,
+    },
     AppState, MenuItems,
 };
 
 pub fn ui<B: Backend>(f: &mut Frame<B>, app_state: &mut AppState) {
     let size = f.size();
 
-    let repo_name = match &app_state.selected_repo {
-        Some(repo) => repo.full_name.clone(),
-        None => String::new(),
-    };
-
-    // A helper closure to create blocks
-    let create_block = |title: &str| {
-        Block::default()
-            .borders(Borders::ALL)
-            .title(title.to_string())
-            .border_type(BorderType::Rounded)
-    };
-
     // The main canvas
     let main = Layout::default()
         .horizontal_margin(2)
@@ -45,80 +37,457 @@ pub fn ui<B: Backend>(f: &mut Frame<B>, app_state: &mut AppState) {
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
         .split(main[1]);
 
-    // Canvas split between showing issues and repos
-    let issues_repos = Layout::default()
+    // Canvas split between showing the menus list and repos
+    let list_repos = Layout::default()
         .constraints([Constraint::Percentage(70), Constraint::Percentage(30)])
         .split(inner[0]);
 
-    f.render_widget(render_menu_bar(&app_state), main[0]);
+    f.render_widget(render_menu_bar(app_state), main[0]);
+
+    // The board spans the full width above the repositories and item details
+    let board_area = Rect {
+        width: main[1].width,
+        ..list_repos[0]
+    };
+    let details_area = Rect {
+        x: inner[1].x,
+        width: inner[1].width,
+        ..list_repos[1]
+    };
+
+    match app_state.current_menu {
+        MenuItems::Issues => render_issues_menu(f, app_state, list_repos[0], inner[1]),
+        MenuItems::Discussions => render_discussions_menu(f, app_state, list_repos[0], inner[1]),
+        MenuItems::Releases => render_releases_menu(f, app_state, list_repos[0], inner[1]),
+        MenuItems::Actions => render_actions_menu(f, app_state, list_repos[0], inner[1]),
+        MenuItems::Board => render_board_menu(f, app_state, board_area, details_area),
+    }
+
+    // The highlighted repository replaces the preview while the repositories are focused
+    if app_state.screen == Screen::Repositories {
+        let preview_area = match app_state.current_menu {
+            MenuItems::Board => details_area,
+            _ => inner[1],
+        };
+
+        if let Some(repo) = app_state.repositories.selected_value() {
+            let overview = render_repository_overview(
+                repo,
+                app_state
+                    .readme_cache
+                    .get(&repo.full_name)
+                    .cloned()
+                    .flatten(),
+            );
+
+            let title = loading_title(
+                app_state,
+                String::from("Repository overview"),
+                app_state.is_loading(|load| matches!(load, Load::Readme(_) | Load::Repository(_))),
+            );
+
+            f.render_widget(Clear, preview_area);
+            f.render_widget(
+                render_markdown(overview.as_str(), title.as_str()),
+                preview_area,
+            );
+        }
+    }
+
+    f.render_stateful_widget(
+        render_list(&app_state.repositories).block(
+            create_block("Repositories")
+                .border_style(focus_style(app_state.screen == Screen::Repositories)),
+        ),
+        list_repos[1],
+        &mut app_state.repositories.state,
+    );
+
+    f.render_widget(render_controls(app_state), main[2]);
+
+    if app_state.popup.show_popup {
+        // Grow the popup to fit multi-line messages
+        let lines = app_state.popup.message.lines().count() as u16 + 2;
+        let percent_y = (lines * 100).div_ceil(size.height.max(1)).clamp(15, 90);
+
+        let area = render_centered_rect(70, percent_y, size);
+        f.render_widget(Clear, area); //this clears out the background
+        f.render_widget(render_popup(&app_state.popup), area)
+    }
+}
+
+fn render_issues_menu<B: Backend>(
+    f: &mut Frame<B>,
+    app_state: &mut AppState,
+    list_area: Rect,
+    preview_area: Rect,
+) {
+    let loading = app_state.is_loading(|load| {
+        matches!(
+            load,
+            Load::Issues(_)
+                | Load::IssuesSelf
+                | Load::Origin(_)
+                | Load::StartWork(..)
+                | Load::Checkout(..)
+                | Load::Branch(..)
+        )
+    });
+    let title = loading_title(
+        app_state,
+        format!("Issues - {}", selected_repo_name(app_state)),
+        loading,
+    );
+    let block =
+        create_block(title.as_str()).border_style(focus_style(app_state.screen == Screen::Issues));
 
     if app_state.issues.items.is_empty() {
         f.render_widget(
-            Paragraph::new("No issues found..").block(
-                create_block(format!("Issues - {}", repo_name).as_str()).border_style(
-                    Style::default().fg(if app_state.screen == Screen::Issues {
-                        Color::Yellow
-                    } else {
-                        Color::White
-                    }),
-                ),
-            ),
-            issues_repos[0],
+            Paragraph::new(if loading {
+                "Loading issues.."
+            } else {
+                "No issues found.."
+            })
+            .block(block),
+            list_area,
         )
     } else {
         f.render_stateful_widget(
-            render_list(&app_state.issues).block(
-                create_block(format!("Issues - {}", repo_name).as_str()).border_style(
-                    Style::default().fg(if app_state.screen == Screen::Issues {
-                        Color::Yellow
-                    } else {
-                        Color::White
-                    }),
-                ),
-            ),
-            issues_repos[0],
+            render_issue_list(app_state).block(block),
+            list_area,
             &mut app_state.issues.state,
         );
     }
 
-    f.render_stateful_widget(
-        render_list(&app_state.repositories).block(create_block("Repositories").border_style(
-            Style::default().fg(if app_state.screen == Screen::Repositories {
-                Color::Yellow
-            } else {
-                Color::White
-            }),
-        )),
-        issues_repos[1],
-        &mut app_state.repositories.state,
+    // Preview canvas split between the issue body and pull request checks
+    let preview = Layout::default()
+        .constraints(if app_state.checks.items.is_empty() {
+            [Constraint::Percentage(100), Constraint::Percentage(0)]
+        } else {
+            [Constraint::Percentage(70), Constraint::Percentage(30)]
+        })
+        .split(preview_area);
+
+    let focused = app_state.screen == Screen::Comments;
+    let issue_preview = match app_state.issues.selected_value() {
+        Some(issue) => render_issue_preview(
+            issue,
+            &app_state.commits,
+            &app_state.comments,
+            focused.then_some(app_state.selected_comment),
+        ),
+        None => String::new(),
+    };
+
+    let title = loading_title(
+        app_state,
+        String::from("Issue preview"),
+        app_state.is_loading(|load| matches!(load, Load::Comments(_))),
     );
+
     f.render_widget(
-        render_markdown(match app_state.issues.selected_value() {
-            Some(issue) => issue.body.as_str(),
-            None => "",
-        }),
-        inner[1],
+        render_markdown(issue_preview.as_str(), title.as_str())
+            .block(create_block(title.as_str()).border_style(focus_style(focused))),
+        preview[0],
     );
-    f.render_widget(render_controls(), main[2]);
 
-    if app_state.popup.show_popup {
-        let area = render_centered_rect(70, 15, size);
-        f.render_widget(Clear, area); //this clears out the background
-        f.render_widget(render_popup(&app_state.popup), area)
+    if !app_state.checks.items.is_empty() {
+        f.render_stateful_widget(
+            render_list(&app_state.checks).block(
+                create_block("Checks")
+                    .border_style(focus_style(app_state.screen == Screen::Checks)),
+            ),
+            preview[1],
+            &mut app_state.checks.state,
+        );
     }
 }
 
-fn render_error_box<'a>(error_text: &'a str) -> Paragraph<'a> {
-    Paragraph::new(error_text).alignment(Alignment::Left).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .border_type(BorderType::Rounded)
-            .title("Error - Press Enter to continue"),
-    )
+fn render_discussions_menu<B: Backend>(
+    f: &mut Frame<B>,
+    app_state: &mut AppState,
+    list_area: Rect,
+    preview_area: Rect,
+) {
+    let title = loading_title(
+        app_state,
+        format!("Discussions - {}", selected_repo_name(app_state)),
+        app_state.is_loading(|load| matches!(load, Load::Discussions(_))),
+    );
+    let block = create_block(title.as_str())
+        .border_style(focus_style(app_state.screen == Screen::Discussions));
+
+    if app_state.discussions.items.is_empty() {
+        f.render_widget(
+            Paragraph::new("No discussions found..").block(block),
+            list_area,
+        )
+    } else {
+        f.render_stateful_widget(
+            render_list(&app_state.discussions).block(block),
+            list_area,
+            &mut app_state.discussions.state,
+        );
+    }
+
+    let focused = app_state.screen == Screen::Comments;
+    let discussion_preview = match app_state.discussions.selected_value() {
+        Some(discussion) => {
+            render_discussion_preview(discussion, focused.then_some(app_state.selected_comment))
+        }
+        None => String::new(),
+    };
+
+    f.render_widget(
+        render_markdown(discussion_preview.as_str(), "Discussion preview")
+            .block(create_block("Discussion preview").border_style(focus_style(focused))),
+        preview_area,
+    );
+}
+
+fn render_releases_menu<B: Backend>(
+    f: &mut Frame<B>,
+    app_state: &mut AppState,
+    list_area: Rect,
+    preview_area: Rect,
+) {
+    let title = loading_title(
+        app_state,
+        format!("Releases - {}", selected_repo_name(app_state)),
+        app_state.is_loading(|load| matches!(load, Load::Releases(_))),
+    );
+    let block = create_block(title.as_str())
+        .border_style(focus_style(app_state.screen == Screen::Releases));
+
+    if app_state.releases.items.is_empty() {
+        f.render_widget(
+            Paragraph::new("No releases found..").block(block),
+            list_area,
+        )
+    } else {
+        f.render_stateful_widget(
+            render_list(&app_state.releases).block(block),
+            list_area,
+            &mut app_state.releases.state,
+        );
+    }
+
+    f.render_widget(
+        render_markdown(
+            app_state
+                .releases
+                .selected_value()
+                .and_then(|release| release.body.as_deref())
+                .unwrap_or(""),
+            "Release notes",
+        ),
+        preview_area,
+    );
+}
+
+fn render_actions_menu<B: Backend>(
+    f: &mut Frame<B>,
+    app_state: &mut AppState,
+    list_area: Rect,
+    preview_area: Rect,
+) {
+    let title = loading_title(
+        app_state,
+        format!("Workflow runs - {}", selected_repo_name(app_state)),
+        app_state.is_loading(|load| matches!(load, Load::Runs(_))),
+    );
+    let block =
+        create_block(title.as_str()).border_style(focus_style(app_state.screen == Screen::Runs));
+
+    if app_state.runs.items.is_empty() {
+        f.render_widget(
+            Paragraph::new("No workflow runs found..").block(block),
+            list_area,
+        )
+    } else {
+        f.render_stateful_widget(
+            render_list(&app_state.runs).block(block),
+            list_area,
+            &mut app_state.runs.state,
+        );
+    }
+
+    // Preview canvas split between the jobs of the opened run and the steps of the selected job
+    let preview = Layout::default()
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(preview_area);
+
+    let title = loading_title(
+        app_state,
+        String::from("Jobs"),
+        app_state.is_loading(|load| matches!(load, Load::Jobs(..))),
+    );
+    let block =
+        create_block(title.as_str()).border_style(focus_style(app_state.screen == Screen::Jobs));
+
+    if app_state.jobs.items.is_empty() {
+        f.render_widget(
+            Paragraph::new("Press Enter on a workflow run to list its jobs..").block(block),
+            preview[0],
+        )
+    } else {
+        f.render_stateful_widget(
+            render_list(&app_state.jobs).block(block),
+            preview[0],
+            &mut app_state.jobs.state,
+        );
+    }
+
+    let steps = match app_state.jobs.selected_value() {
+        Some(job) => job
+            .steps
+            .iter()
+            .map(|step| ListItem::new(step.to_string()))
+            .collect(),
+        None => vec![],
+    };
+
+    f.render_widget(List::new(steps).block(create_block("Steps")), preview[1]);
+}
+
+fn render_board_menu<B: Backend>(
+    f: &mut Frame<B>,
+    app_state: &mut AppState,
+    board_area: Rect,
+    details_area: Rect,
+) {
+    let focused = app_state.screen == Screen::Board;
+    let loading = app_state.is_loading(|load| matches!(load, Load::Projects(_)));
+
+    let Some(board) = app_state.board.as_mut() else {
+        let title = loading_title(
+            app_state,
+            format!("Board - {}", selected_repo_name(app_state)),
+            loading,
+        );
+
+        f.render_widget(
+            Paragraph::new("No projects found..")
+                .block(create_block(title.as_str()).border_style(focus_style(focused))),
+            board_area,
+        );
+        f.render_widget(create_block("Item"), details_area);
+        return;
+    };
+
+    let block = create_block(format!("Board - {}", board.project.title).as_str())
+        .border_style(focus_style(focused));
+    let columns_area = block.inner(board_area);
+    f.render_widget(block, board_area);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(
+            board
+                .columns
+                .iter()
+                .map(|_| Constraint::Ratio(1, board.columns.len() as u32))
+                .collect::<Vec<Constraint>>(),
+        )
+        .split(columns_area);
+
+    let selected_column = board.selected_column;
+
+    for (index, (column, area)) in board.columns.iter_mut().zip(columns.iter()).enumerate() {
+        let selected = index == selected_column;
+
+        // Only the selected column shows its selected card
+        let cards = List::new(
+            column
+                .items
+                .items
+                .iter()
+                .map(|item| ListItem::new(vec![Spans::from(item.to_string()), Spans::from("")]))
+                .collect::<Vec<ListItem>>(),
+        )
+        .highlight_style(Style::default().fg(if selected {
+            Color::LightGreen
+        } else {
+            Color::Reset
+        }))
+        .block(
+            create_block(format!("{} ({})", column.name, column.items.items.len()).as_str())
+                .border_style(focus_style(focused && selected)),
+        );
+
+        f.render_stateful_widget(cards, *area, &mut column.items.state);
+    }
+
+    let details = match board.selected_item() {
+        Some(item) => board
+            .editable_fields()
+            .iter()
+            .map(|field| {
+                let value = field
+                    .id()
+                    .and_then(|id| item.value(id))
+                    .and_then(|value| {
+                        field
+                            .options()
+                            .into_iter()
+                            .find(|option| option.id == value)
+                    })
+                    .map(|option| option.name)
+                    .unwrap_or(String::from("-"));
+
+                ListItem::new(format!("{}: {}", field, value))
+            })
+            .collect(),
+        None => vec![],
+    };
+
+    f.render_widget(List::new(details).block(create_block("Item")), details_area);
+}
+
+/// `title` followed by the loading spinner while the pane is `loading`.
+fn loading_title(app_state: &AppState, title: String, loading: bool) -> String {
+    if loading {
+        format!("{} {}", title, app_state.spinner())
+    } else {
+        title
+    }
+}
+
+fn selected_repo_name(app_state: &AppState) -> String {
+    match &app_state.selected_repo {
+        Some(repo) => repo.full_name.clone(),
+        None => String::new(),
+    }
+}
+
+/// A helper function to create blocks
+fn create_block<'a>(title: &str) -> Block<'a> {
+    Block::default()
+        .borders(Borders::ALL)
+        .title(title.to_string())
+        .border_type(BorderType::Rounded)
+}
+
+/// Highlight the border of the focused pane
+fn focus_style(focused: bool) -> Style {
+    Style::default().fg(if focused { Color::Yellow } else { Color::White })
+}
+
+/// Draw the first-run setup on its own, before the app is loaded.
+pub fn setup_ui<B: Backend>(f: &mut Frame<B>, wizard: &SetupWizard) {
+    let popup = Popup {
+        title: String::from("Setup"),
+        message: wizard.to_string(),
+        show_popup: true,
+    };
+
+    let area = render_centered_rect(70, 50, f.size());
+    f.render_widget(render_popup(&popup), area)
 }
 
 fn render_popup<'a>(popup: &Popup) -> Paragraph<'a> {
     Paragraph::new(popup.message.clone())
+        .wrap(Wrap { trim: false })
         .alignment(Alignment::Left)
         .block(
             Block::default()
@@ -135,7 +504,7 @@ fn parse_markdown_headers(content: &str) -> String {
             let mut parsed_content = String::new();
 
             // Check if line starts with '#'
-            if line.chars().nth(0).unwrap_or(' ') != '#' {
+            if line.chars().next().unwrap_or(' ') != '#' {
                 return format!("{}\n", line);
             }
 
@@ -173,6 +542,168 @@ fn parse_markdown_headers(content: &str) -> String {
         .join("")
 }
 
+/// Build the markdown shown in the preview pane for a discussion and its replies, marking the
+/// `selected` comment.
+fn render_discussion_preview(discussion: &Discussion, selected: Option<usize>) -> String {
+    let mut preview = format!(
+        "**{}** in {}\n\n{}",
+        discussion
+            .author
+            .as_ref()
+            .map(|author| author.login.as_str())
+            .unwrap_or("ghost"),
+        discussion.category.name,
+        discussion.body
+    );
+
+    if !discussion.comments.nodes.is_empty() {
+        preview.push_str("\n\n## Replies\n");
+
+        for (index, comment) in discussion.comments.nodes.iter().enumerate() {
+            preview.push_str(
+                format!(
+                    "\n{}**{}**{}\n\n{}\n",
+                    if selected == Some(index) { "▶ " } else { "" },
+                    comment.author_login(),
+                    if comment.is_answer { " ✔ answer" } else { "" },
+                    comment.body
+                )
+                .as_str(),
+            );
+
+            // Threaded replies are quoted under their comment
+            for reply in &comment.replies.nodes {
+                preview.push_str(format!("\n> **{}**\n", reply.author_login()).as_str());
+
+                for line in reply.body.lines() {
+                    preview.push_str(format!("> {}\n", line).as_str());
+                }
+            }
+        }
+    }
+
+    preview
+}
+
+/// Build the markdown shown in the preview pane for a repository and its README.
+fn render_repository_overview(repo: &Repository, readme: Option<String>) -> String {
+    let mut overview = format!("# {}\n", repo.full_name);
+
+    if let Some(description) = repo.description.as_ref().filter(|text| !text.is_empty()) {
+        overview.push_str(format!("\n{}\n", description).as_str());
+    }
+
+    if !repo.topics.is_empty() {
+        overview.push_str(
+            format!(
+                "\n{}\n",
+                repo.topics
+                    .iter()
+                    .map(|topic| format!("`{}`", topic))
+                    .collect::<Vec<String>>()
+                    .join(" ")
+            )
+            .as_str(),
+        );
+    }
+
+    // The details of repositories only known by name are still loading
+    if !repo.placeholder {
+        overview.push_str(
+            format!(
+                "\n★ {} stars · {} forks · default branch `{}`\n",
+                repo.stargazers_count, repo.forks_count, repo.default_branch
+            )
+            .as_str(),
+        );
+    }
+
+    if let Some(license) = &repo.license {
+        overview.push_str(format!("\nLicense: {}\n", license.name).as_str());
+    }
+
+    if let Some(pushed_at) = &repo.pushed_at {
+        // Only keep the date of the timestamp
+        let date = pushed_at.split('T').next().unwrap_or(pushed_at);
+        overview.push_str(format!("\nLast push: {}\n", date).as_str());
+    }
+
+    overview.push_str("\n---\n\n");
+    overview.push_str(readme.as_deref().unwrap_or("No README found.."));
+
+    overview
+}
+
+/// Build the markdown shown in the preview pane for an issue, marking the `selected` comment.
+fn render_issue_preview(
+    issue: &Issue,
+    commits: &[Commit],
+    comments: &[Comment],
+    selected: Option<usize>,
+) -> String {
+    let mut details = issue
+        .labels
+        .iter()
+        .map(|label| format!("`{}`", label.name))
+        .collect::<Vec<String>>();
+
+    if let Some(milestone) = &issue.milestone {
+        details.push(format!("Milestone: {}", milestone.title));
+    }
+
+    let mut preview = if details.is_empty() {
+        issue.body.clone()
+    } else {
+        format!("{}\n\n{}", details.join(" "), issue.body)
+    };
+
+    if !comments.is_empty() {
+        preview.push_str("\n\n## Comments\n");
+
+        for (index, comment) in comments.iter().enumerate() {
+            preview.push_str(
+                format!(
+                    "\n{}**{}** on {}\n\n{}\n",
+                    if selected == Some(index) { "▶ " } else { "" },
+                    comment.user.login,
+                    // Only keep the date of the timestamp
+                    comment.created_at.split('T').next().unwrap_or(""),
+                    comment.body
+                )
+                .as_str(),
+            );
+        }
+    }
+
+    if !commits.is_empty() {
+        preview.push_str("\n\n## Local commits\n\n");
+
+        for commit in commits {
+            preview.push_str(
+                format!(
+                    "* `{}` **{}** {}\n",
+                    commit.short_sha, commit.author, commit.subject
+                )
+                .as_str(),
+            );
+        }
+    }
+
+    if !issue.reviews.is_empty() {
+        preview.push_str("\n\n## Reviews\n");
+
+        for review in &issue.reviews {
+            preview.push_str(format!("\n**{}**\n", review).as_str());
+
+            if !review.body.is_empty() {
+                preview.push_str(format!("\n{}\n", review.body).as_str());
+            }
+        }
+    }
+
+    preview
+}
+
 fn render_list<'a, T: std::fmt::Display>(items: &StatefulList<T>) -> List<'a> {
     let items: Vec<ListItem> = items
         .items
@@ -185,11 +716,34 @@ fn render_list<'a, T: std::fmt::Display>(items: &StatefulList<T>) -> List<'a> {
         .start_corner(Corner::TopLeft)
 }
 
-fn render_markdown<'a>(content: &'a str) -> Paragraph<'a> {
+/// Issues with a badge in front of the ones that are new or changed since they were last viewed.
+fn render_issue_list<'a>(app_state: &AppState) -> List<'a> {
+    let items: Vec<ListItem> = app_state
+        .issues
+        .items
+        .iter()
+        .map(|issue| {
+            if app_state.unseen_issues.contains(&issue.html_url) {
+                ListItem::new(Spans::from(vec![
+                    Span::styled("● ", Style::default().fg(Color::Yellow)),
+                    Span::raw(issue.to_string()),
+                ]))
+            } else {
+                ListItem::new(issue.to_string())
+            }
+        })
+        .collect();
+
+    List::new(items)
+        .highlight_style(Style::default().fg(Color::LightGreen))
+        .start_corner(Corner::TopLeft)
+}
+
+fn render_markdown<'a>(content: &'a str, title: &'a str) -> Paragraph<'a> {
     let parsed_content = parse_markdown_headers(content);
 
     // Convert md content to ansi string
-    let output = termimad::text(&parsed_content.as_str())
+    let output = termimad::text(parsed_content.as_str())
         .to_string()
         // Convert ansi string to tui::text::Text
         .into_text()
@@ -201,13 +755,19 @@ fn render_markdown<'a>(content: &'a str) -> Paragraph<'a> {
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title("Issue preview")
+                .title(title)
                 .border_type(BorderType::Rounded),
         )
 }
 
-fn render_controls<'a>() -> Paragraph<'a> {
-    Paragraph::new("q: quit, Up / k && Down / j: scroll list, Enter: open/select issue/repository, Tab: switch focus, S: search repo, M: all user issues")
+fn render_controls<'a>(app_state: &AppState) -> Paragraph<'a> {
+    Paragraph::new(match app_state.current_menu {
+        MenuItems::Issues => "q: quit, Up / k && Down / j: scroll list, Enter: open/select issue/repository, Tab: switch focus (issues, repositories, checks, comments), S: search repo, M: all user issues, R: review pull request, G: merge pull request, O: check out pull request, W: start work branch, U: author profile of issue/focused comment, N: new issue, C: comment, P: switch profile, Ctrl-r: refresh",
+        MenuItems::Discussions => "q: quit, Up / k && Down / j: scroll list, Enter: open/select discussion/repository, Tab: switch focus (discussions, repositories, comments), S: search repo, C: reply to discussion/focused comment, U: author profile of discussion/focused comment, P: switch profile",
+        MenuItems::Releases => "q: quit, Up / k && Down / j: scroll list, Enter: open/select release/repository, Tab: switch focus, S: search repo, P: switch profile",
+        MenuItems::Actions => "q: quit, Up / k && Down / j: scroll list, Enter: open run jobs/job/repository, Tab: switch focus (runs, repositories, jobs), S: search repo, R: re-run failed jobs, X: cancel run, L: job logs, P: switch profile",
+        MenuItems::Board => "q: quit, h / l: switch column, Up / k && Down / j: scroll column, H / L: move item, F: edit field, N: next project, Enter: open item/select repository, Tab: switch focus, S: search repo, P: switch profile",
+    })
         .wrap(Wrap { trim: false })
         .alignment(Alignment::Left)
 }
@@ -237,6 +797,8 @@ fn render_menu_bar<'a>(app_state: &AppState) -> Paragraph<'a> {
 
     Paragraph::new(items).alignment(Alignment::Left).block(
         Block::default()
+            .title(format!(" Profile: {} ", app_state.config.active_profile))
+            .title_alignment(Alignment::Right)
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded),
     )